@@ -0,0 +1,135 @@
+//! Throughput of the crossover operators, repair included.
+//!
+//! Run with `cargo bench --bench crossover`. Each line reports the mean time of one call to
+//! `Crossover::crossover` with crossover rate `1.0` on a random graph with average degree
+//! about 8, so results can be compared across commits on the same machine. `unsafe-splice`
+//! is single-point crossover as it was before the safe slice copies; it is the baseline for
+//! the `single-point` line.
+
+use std::{hint::black_box, time::Instant};
+
+use cl_total_rdga::{
+    csr::CsrGraph,
+    genetic::{Chromosome, Crossover, Neighborhood, SinglePoint, TwoPoint, Uniform},
+};
+use kambo_graph::{graphs::simple::UndirectedGraph, Graph, GraphMut};
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+
+/// A cycle on `order` vertices plus random chords, so no vertex is isolated.
+fn random_graph(order: usize, rng: &mut ChaCha8Rng) -> UndirectedGraph<usize> {
+    let mut graph = UndirectedGraph::<usize>::new_undirected();
+    for v in 0..order {
+        graph.add_vertex(v).unwrap();
+    }
+    for v in 0..order {
+        graph.add_edge(&v, &((v + 1) % order)).ok();
+    }
+    for _ in 0..3 * order {
+        let (u, v) = (rng.gen_range(0..order), rng.gen_range(0..order));
+        if u != v && !graph.contains_edge(&u, &v) {
+            graph.add_edge(&u, &v).unwrap();
+        }
+    }
+    graph
+}
+
+/// Single-point crossover that splices the genes with raw pointer copies into uninitialized
+/// vectors, kept unchanged from the former `SinglePoint`.
+struct UnsafeSplice;
+
+impl Crossover for UnsafeSplice {
+    #[allow(clippy::uninit_vec)]
+    fn crossover(
+        &self,
+        parent1: &Chromosome,
+        parent2: &Chromosome,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
+    ) -> (Chromosome, Chromosome) {
+        if !rng.gen_bool(1.0) {
+            return (
+                Chromosome::new(parent1.genes().to_vec()),
+                Chromosome::new(parent2.genes().to_vec()),
+            );
+        }
+
+        let genes1 = parent1.genes();
+        let genes2 = parent2.genes();
+        let len = genes1.len();
+        let point = rng.gen_range(1..len);
+
+        let mut child1_genes = Vec::with_capacity(len);
+        let mut child2_genes = Vec::with_capacity(len);
+
+        // SAFETY: both vectors have capacity `len`, every position is written below before it
+        // is read, and `point < len` keeps the copies inside the parents.
+        unsafe {
+            child1_genes.set_len(len);
+            child2_genes.set_len(len);
+
+            // First child
+            std::ptr::copy_nonoverlapping(genes1.as_ptr(), child1_genes.as_mut_ptr(), point);
+            std::ptr::copy_nonoverlapping(
+                genes2.as_ptr().add(point),
+                child1_genes.as_mut_ptr().add(point),
+                len - point,
+            );
+
+            // Second child
+            std::ptr::copy_nonoverlapping(genes2.as_ptr(), child2_genes.as_mut_ptr(), point);
+            std::ptr::copy_nonoverlapping(
+                genes1.as_ptr().add(point),
+                child2_genes.as_mut_ptr().add(point),
+                len - point,
+            );
+        }
+
+        let mut child1 = Chromosome::new(child1_genes);
+        let mut child2 = Chromosome::new(child2_genes);
+
+        child1.fix(graph);
+        child2.fix(graph);
+
+        (child1, child2)
+    }
+}
+
+fn random_parent(csr: &CsrGraph, rng: &mut ChaCha8Rng) -> Chromosome {
+    let genes = (0..csr.order()).map(|_| rng.gen_range(0..=2)).collect();
+    let mut chromosome = Chromosome::new(genes);
+    chromosome.fix(csr);
+    chromosome
+}
+
+fn main() {
+    let operators: [(&str, Box<dyn Crossover>); 5] = [
+        ("unsafe-splice", Box::new(UnsafeSplice)),
+        ("single-point", Box::new(SinglePoint::new(1.0))),
+        ("two-point", Box::new(TwoPoint::new(1.0))),
+        ("uniform", Box::new(Uniform::new(1.0))),
+        ("neighborhood", Box::new(Neighborhood::new(1.0))),
+    ];
+
+    for order in [100, 1_000, 10_000] {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let csr = CsrGraph::new(&random_graph(order, &mut rng));
+        let parent1 = random_parent(&csr, &mut rng);
+        let parent2 = random_parent(&csr, &mut rng);
+        let iterations = 2_000_000 / order;
+
+        for (name, operator) in &operators {
+            for _ in 0..iterations / 10 {
+                black_box(operator.crossover(&parent1, &parent2, &csr, &mut rng));
+            }
+
+            let start = Instant::now();
+            for _ in 0..iterations {
+                black_box(operator.crossover(&parent1, &parent2, &csr, &mut rng));
+            }
+            let per_call = start.elapsed().as_secs_f64() * 1e9 / iterations as f64;
+
+            println!("{name:<13} n = {order:>6}  {per_call:>12.0} ns/crossover");
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! Cost of one tournament on populations of large instances.
+//!
+//! Run with `cargo bench --bench selection`. The graphs are random graphs with the order and
+//! density of `brock800_1` and `p_hat1500-1`, so the chromosomes have the same length as on
+//! those instances. `summing` recomputes each contestant's weight from its genes, as selection
+//! did before the weight was cached in `Chromosome`; it is the baseline for the other lines.
+
+use std::{hint::black_box, time::Instant};
+
+use cl_total_rdga::{
+    csr::CsrGraph,
+    genetic::{h2, h5, Chromosome, KTournament, Population, Selection},
+};
+use kambo_graph::{graphs::simple::UndirectedGraph, GraphMut};
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+
+const POP_SIZE: usize = 50;
+const TOURNAMENT_SIZE: usize = 5;
+const ITERATIONS: usize = 200_000;
+
+/// Tournament that sums the genes of every contestant instead of reading the cached weight.
+struct Summing {
+    k: usize,
+}
+
+impl Selection for Summing {
+    fn select<'a>(&self, population: &'a Population, rng: &mut dyn RngCore) -> &'a Chromosome {
+        let chromosomes = population.chromosomes();
+        let best = (0..self.k)
+            .map(|_| rng.gen_range(0..chromosomes.len()))
+            .min_by_key(|&idx| {
+                chromosomes[idx]
+                    .genes()
+                    .iter()
+                    .map(|&gene| usize::from(gene))
+                    .sum::<usize>()
+            })
+            .unwrap_or(0);
+        &chromosomes[best]
+    }
+}
+
+fn random_graph(order: usize, density: f64, rng: &mut ChaCha8Rng) -> UndirectedGraph<usize> {
+    let mut graph = UndirectedGraph::<usize>::new_undirected();
+    for v in 0..order {
+        graph.add_vertex(v).unwrap();
+    }
+    for u in 0..order {
+        for v in u + 1..order {
+            if rng.gen_bool(density) {
+                graph.add_edge(&u, &v).unwrap();
+            }
+        }
+    }
+    graph
+}
+
+fn main() {
+    let selectors: [(&str, Box<dyn Selection>); 3] = [
+        ("summing", Box::new(Summing { k: TOURNAMENT_SIZE })),
+        ("tournament", Box::new(KTournament::new(TOURNAMENT_SIZE))),
+        (
+            "distinct",
+            Box::new(KTournament::new(TOURNAMENT_SIZE).without_replacement(true)),
+        ),
+    ];
+
+    for (name, order, density) in [("brock800", 800, 0.65), ("p-hat1500-1", 1500, 0.25)] {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let csr = CsrGraph::new(&random_graph(order, density, &mut rng));
+        let population = Population::new(POP_SIZE, &[h2, h5], &csr, &mut rng);
+
+        for (selector_name, selector) in &selectors {
+            let start = Instant::now();
+            for _ in 0..ITERATIONS {
+                black_box(selector.select(&population, &mut rng));
+            }
+            let per_call = start.elapsed().as_secs_f64() * 1e9 / ITERATIONS as f64;
+
+            println!("{name:<12} {selector_name:<11} {per_call:>10.1} ns/selection");
+        }
+    }
+}
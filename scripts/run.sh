@@ -6,29 +6,36 @@ GENERATIONS=1000
 TOURNAMENT_SIZE=5
 CROSSOVER_PROB=0.9
 POP_SIZE=50
+TRIALS=30
+RESUME=()
 
 # Help function
 show_help() {
-  echo "Usage: $0 [-s max_stagnant] [-g generations] [-t tournament_size] [-c crossover_prob] [-p pop_size]"
+  echo "Usage: $0 [-s max_stagnant] [-g generations] [-t tournament_size] [-c crossover_prob] [-p pop_size] [-n trials] [-r]"
   echo "Options:"
   echo "  -s: Maximum stagnant generations (default: 100)"
   echo "  -g: Maximum generations (default: 1000)"
   echo "  -t: Tournament size (default: 5)"
   echo "  -c: Crossover probability (default: 0.9)"
   echo "  -p: Population size (default: 50)"
-  exit 1
+  echo "  -n: Trials per graph (default: 30)"
+  echo "  -r: Resume, running only the trials missing from data/results"
+  echo "  -h: Show this help"
+  exit "$1"
 }
 
 # Parse command line options
-while getopts "h:s:g:t:c:p:" opt; do
+while getopts "hs:g:t:c:p:n:r" opt; do
   case $opt in
-  h) show_help ;;
+  h) show_help 0 ;;
   s) MAX_STAGNANT=$OPTARG ;;
   g) GENERATIONS=$OPTARG ;;
   t) TOURNAMENT_SIZE=$OPTARG ;;
   c) CROSSOVER_PROB=$OPTARG ;;
   p) POP_SIZE=$OPTARG ;;
-  ?) show_help ;;
+  n) TRIALS=$OPTARG ;;
+  r) RESUME=(--resume) ;;
+  ?) show_help 1 ;;
   esac
 done
 
@@ -50,67 +57,15 @@ if [ ! -f "$PROJECT_ROOT/target/release/cl-total-rdga" ]; then
   exit 1
 fi
 
-# Create results directory if it doesn't exist
-mkdir -p "$PROJECT_ROOT/data/results"
-
-# Create a temporary file to store file sizes and paths
-temp_file=$(mktemp)
-
-# Check if there are any .txt files
-edge_files=("$PROJECT_ROOT/data/edges/"*.txt)
-if [ ! -e "${edge_files[0]}" ]; then
-  echo "Error: No .txt files found in $PROJECT_ROOT/data/edges/"
-  rm "$temp_file"
-  exit 1
-fi
-
-# Get all .txt files and their sizes, sort by size
-for edge_file in "$PROJECT_ROOT"/data/edges/*.txt; do
-  if [ -f "$edge_file" ]; then
-    # Count number of lines in file
-    num_lines=$(wc -l <"$edge_file")
-    echo "$num_lines $edge_file" >>"$temp_file"
-  fi
-done
-
-# Sort files by size (number of lines)
-sorted_files=$(sort -n "$temp_file" | cut -d' ' -f2-)
-
-# Remove temporary file
-rm "$temp_file"
-
-# Get total number of files
-total_files=$(echo "$sorted_files" | wc -l)
-current_file=0
-
-echo "Found $total_files edge list files to process"
-echo "Starting processing..."
-echo "----------------------------------------"
-
-# Process each file in sorted order
-echo "$sorted_files" | while read -r edge_file; do
-  ((current_file++))
-  filename=$(basename "$edge_file")
-  filename_no_ext="${filename%.txt}"
-  output_file="$PROJECT_ROOT/data/results/${filename_no_ext}.csv"
-  num_lines=$(wc -l <"$edge_file")
-
-  echo "Processing file ${current_file}/${total_files}: $filename (Lines: $num_lines)"
-  echo "Output will be saved to: $output_file"
-
-  # Execute the algorithm with full paths
-  "$PROJECT_ROOT/target/release/cl-total-rdga" \
-    "$edge_file" \
-    30 \
-    "$output_file"
-
-  # Check if the execution was successful
-  if [ $? -ne 0 ]; then
-    echo "Error processing $filename"
-    echo "Command failed with exit code $?"
-  fi
-
-  echo "----------------------------------------"
-done
-
-echo "Processing complete!"
+# The binary orders the graphs by size, shares one thread pool among them and writes
+# data/results/<graph>.csv for each graph plus data/results.csv with every row.
+exec "$PROJECT_ROOT/target/release/cl-total-rdga" batch \
+  --instances "$PROJECT_ROOT/data/edges/*.txt" \
+  --output-dir "$PROJECT_ROOT/data/results" \
+  --trials "$TRIALS" \
+  --max-stagnant "$MAX_STAGNANT" \
+  --generations "$GENERATIONS" \
+  --tournament-size "$TOURNAMENT_SIZE" \
+  --crossover-rate "$CROSSOVER_PROB" \
+  --pop-size "$POP_SIZE" \
+  "${RESUME[@]}"
@@ -0,0 +1,140 @@
+use std::{fs, path::Path};
+
+/// Expands `paths` into graph files ordered by size, smallest first, so the quick instances
+/// finish before the long ones. Each path may be a file, a directory (all of its files) or a
+/// pattern with `*` and `?` wildcards in its last component, such as `data/edges/*.txt`.
+/// Hidden files are skipped and a file reached twice is kept once.
+pub fn instance_files(paths: &[String]) -> Result<Vec<String>, String> {
+    let mut files = Vec::new();
+    for path in paths {
+        let matched = if has_wildcards(path) {
+            let (dir, pattern) = match path.rsplit_once('/') {
+                Some((dir, pattern)) => (if dir.is_empty() { "/" } else { dir }, pattern),
+                None => (".", path.as_str()),
+            };
+            if has_wildcards(dir) {
+                return Err(format!(
+                    "invalid pattern '{path}': wildcards are only allowed in the file name"
+                ));
+            }
+            let matched = dir_files(dir, |name| matches(pattern, name))?;
+            if matched.is_empty() {
+                return Err(format!("no file matches '{path}'"));
+            }
+            matched
+        } else if Path::new(path).is_dir() {
+            dir_files(path, |_| true)?
+        } else if Path::new(path).is_file() {
+            vec![path.clone()]
+        } else {
+            return Err(format!(
+                "'{path}' is neither a file, a directory nor a pattern"
+            ));
+        };
+        files.extend(matched);
+    }
+
+    let mut sized = Vec::with_capacity(files.len());
+    for file in files {
+        let len = fs::metadata(&file)
+            .map_err(|e| format!("failed to read '{file}': {e}"))?
+            .len();
+        sized.push((len, file));
+    }
+    sized.sort();
+    sized.dedup_by(|a, b| a.1 == b.1);
+
+    Ok(sized.into_iter().map(|(_, file)| file).collect())
+}
+
+fn has_wildcards(path: &str) -> bool {
+    path.contains(['*', '?'])
+}
+
+/// Returns the non-hidden files of `dir` whose name passes `keep`.
+fn dir_files(dir: &str, keep: impl Fn(&str) -> bool) -> Result<Vec<String>, String> {
+    let entries = fs::read_dir(dir).map_err(|e| format!("failed to read '{dir}': {e}"))?;
+    Ok(entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|p| p.is_file())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| !name.starts_with('.') && keep(name))
+        })
+        .map(|p| p.to_string_lossy().into_owned())
+        .collect())
+}
+
+/// Matches `name` against `pattern`, where `*` matches any run of characters and `?` exactly
+/// one.
+fn matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    // Backtracks only to the last `*`, which is enough for this kind of pattern.
+    let (mut p, mut n) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn star_matches_any_run() {
+        assert!(matches("*.txt", "graph.txt"));
+        assert!(matches("*.txt", ".txt"));
+        assert!(matches("g*h.txt", "graph.txt"));
+        assert!(matches("*a*a*", "banana"));
+        assert!(!matches("*.txt", "graph.clq"));
+    }
+
+    #[test]
+    fn question_mark_matches_one_character() {
+        assert!(matches("g?.txt", "g1.txt"));
+        assert!(!matches("g?.txt", "g.txt"));
+        assert!(!matches("g?.txt", "g12.txt"));
+        assert!(matches("??", "ab"));
+    }
+
+    #[test]
+    fn trailing_star_matches_the_rest() {
+        assert!(matches("p_hat*", "p_hat1500-3.clq"));
+        assert!(matches("p_hat*", "p_hat"));
+        assert!(matches("p_hat**", "p_hat"));
+        assert!(!matches("p_hat*", "hat"));
+    }
+
+    #[test]
+    fn no_match() {
+        assert!(!matches("graph.txt", "graph.txt.bak"));
+        assert!(!matches("graph.txt", "graph"));
+        assert!(!matches("a*b", "ac"));
+        assert!(!matches("", "graph.txt"));
+        assert!(matches("", ""));
+    }
+}
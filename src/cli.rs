@@ -0,0 +1,788 @@
+use std::{ffi::OsString, path::Path, time::Duration};
+
+use clap::{error::ErrorKind, Args, CommandFactory, Parser, Subcommand, ValueEnum};
+
+use crate::config::{
+    AlgorithmParams, CrossoverKind, ExperimentConfig, HeuristicKind, MutationKind, ReportFormat,
+    SelectionKind, StopRule, MAX_SEED,
+};
+
+/// Genetic algorithm for the total Roman domination problem
+#[derive(Debug, Parser)]
+#[command(name = env!("CARGO_PKG_NAME"))]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run the genetic algorithm and write one CSV row per trial
+    Solve(SolveArgs),
+    /// Run the genetic algorithm on many graphs, smallest first, with the same settings
+    Batch(BatchArgs),
+    /// Time the genetic algorithm on a graph without writing results
+    Bench(BenchArgs),
+    /// Summarize result CSVs per graph as a CSV, Markdown or LaTeX table
+    Report(ReportArgs),
+    /// Check a labeling file against a graph, listing every violated constraint
+    Validate(ValidateArgs),
+    /// Compute the total Roman domination number of a small graph by branch and bound
+    Exact(ExactArgs),
+    /// Print statistics about a graph
+    Info(InfoArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct SolveArgs {
+    /// Graph file, as an edge list or in DIMACS format
+    #[arg(short, long, value_name = "FILE", value_parser = existing_file)]
+    pub graph: String,
+    /// Number of independent trials [default: 1]
+    #[arg(short = 'n', long, value_name = "N", value_parser = at_least_one)]
+    trials: Option<usize>,
+    /// Run only trial N of the --trials, drawing the same numbers as in the full run
+    #[arg(long, value_name = "N", value_parser = at_least_one, conflicts_with = "restore")]
+    trial: Option<usize>,
+    /// CSV file the results are appended to [default: stdout]
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<String>,
+    /// Directory where the best labeling of each trial is written as <graph>-<seed>-<trial>.sol
+    #[arg(long, value_name = "DIR")]
+    pub solutions: Option<String>,
+    /// Per-generation trace of every trial, as JSON Lines for .jsonl/.json files, else CSV
+    #[arg(long, value_name = "FILE")]
+    pub trace: Option<String>,
+    /// Run only the trials missing from --output, reusing its base seed unless --seed is given
+    #[arg(long, requires = "output")]
+    pub resume: bool,
+    /// Directory where each trial saves its state as <graph>-<seed>-<trial>.ckpt
+    #[arg(long, value_name = "DIR")]
+    pub checkpoint: Option<String>,
+    /// Generations between two checkpoints
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 100,
+        value_parser = at_least_one,
+        requires = "checkpoint"
+    )]
+    pub checkpoint_every: usize,
+    /// Continue the trial saved in a checkpoint, with the options of the interrupted run
+    #[arg(
+        long,
+        value_name = "FILE",
+        value_parser = existing_file,
+        conflicts_with_all = ["resume", "trials"]
+    )]
+    pub restore: Option<String>,
+    /// Solve the graph without its isolated vertices instead of failing, reporting them apart
+    #[arg(long)]
+    pub drop_isolated: bool,
+    #[command(flatten)]
+    ga: GaOptions,
+    /// Settings resolved from the defaults, `--config` and the options above.
+    #[arg(skip)]
+    pub config: ExperimentConfig,
+}
+
+#[derive(Debug, Args)]
+pub struct BatchArgs {
+    /// Comma-separated graph files, directories or patterns like 'data/edges/*.txt'
+    #[arg(
+        short,
+        long,
+        value_name = "PATHS",
+        required = true,
+        value_delimiter = ',',
+        value_parser = trimmed
+    )]
+    pub instances: Vec<String>,
+    /// Number of independent trials per graph [default: 1]
+    #[arg(short = 'n', long, value_name = "N", value_parser = at_least_one)]
+    trials: Option<usize>,
+    /// Directory where the results of each graph are appended to <graph>.csv
+    #[arg(short, long, value_name = "DIR")]
+    pub output_dir: String,
+    /// CSV file the results of every graph are appended to [default: <DIR>.csv]
+    #[arg(long, value_name = "FILE")]
+    pub combined: Option<String>,
+    /// Directory where the best labeling of each trial is written as <graph>-<seed>-<trial>.sol
+    #[arg(long, value_name = "DIR")]
+    pub solutions: Option<String>,
+    /// Worker threads shared by all graphs, 0 for one per core
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    pub threads: usize,
+    /// Run only the trials missing from the result files of each graph
+    #[arg(long)]
+    pub resume: bool,
+    /// Solve the graphs without their isolated vertices instead of failing, reporting them apart
+    #[arg(long)]
+    pub drop_isolated: bool,
+    #[command(flatten)]
+    ga: GaOptions,
+    /// Settings resolved from the defaults, `--config` and the options above.
+    #[arg(skip)]
+    pub config: ExperimentConfig,
+}
+
+#[derive(Debug, Args)]
+pub struct BenchArgs {
+    /// Graph file, as an edge list or in DIMACS format
+    #[arg(short, long, value_name = "FILE", value_parser = existing_file)]
+    pub graph: String,
+    /// Number of timed trials [default: 5]
+    #[arg(short = 'n', long, value_name = "N", value_parser = at_least_one)]
+    trials: Option<usize>,
+    /// Solve the graph without its isolated vertices instead of failing, reporting them apart
+    #[arg(long)]
+    pub drop_isolated: bool,
+    #[command(flatten)]
+    ga: GaOptions,
+    /// Settings resolved from the defaults, `--config` and the options above.
+    #[arg(skip)]
+    pub config: ExperimentConfig,
+}
+
+#[derive(Debug, Args)]
+pub struct ReportArgs {
+    /// Comma-separated result CSV files or directories of them
+    #[arg(
+        short,
+        long,
+        value_name = "PATHS",
+        required = true,
+        value_delimiter = ',',
+        value_parser = trimmed
+    )]
+    pub results: Vec<String>,
+    /// File with one 'graph optimum' pair per line, for the success rate
+    #[arg(long, value_name = "FILE", value_parser = existing_file)]
+    pub optima: Option<String>,
+    /// Table format
+    #[arg(short, long, value_name = "NAME", default_value = "csv")]
+    pub format: ReportFormat,
+    /// File the report is written to [default: stdout]
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct ValidateArgs {
+    /// Graph file, as an edge list or in DIMACS format
+    #[arg(short, long, value_name = "FILE", value_parser = existing_file)]
+    pub graph: String,
+    /// Labeling file with one 'vertex label' pair per line; missing vertices are 0
+    #[arg(short, long, value_name = "FILE", value_parser = existing_file)]
+    pub labeling: String,
+}
+
+#[derive(Debug, Args)]
+pub struct ExactArgs {
+    /// Graph file, as an edge list or in DIMACS format
+    #[arg(short, long, value_name = "FILE", value_parser = existing_file)]
+    pub graph: String,
+    /// Stop after N branch-and-bound nodes, printing the best weight and a lower bound
+    #[arg(long, value_name = "N", value_parser = at_least_one)]
+    pub node_limit: Option<usize>,
+    /// File the best labeling is written to, in the format read by 'validate'
+    #[arg(long, value_name = "FILE")]
+    pub solution: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct InfoArgs {
+    /// Graph file, as an edge list or in DIMACS format
+    #[arg(short, long, value_name = "FILE", value_parser = existing_file)]
+    pub graph: String,
+}
+
+/// Options of the genetic algorithm shared by `solve`, `batch` and `bench`. Each one that is
+/// given overrides the value read from `--config`.
+#[derive(Debug, Args)]
+struct GaOptions {
+    /// TOML or JSON experiment config; options given here override it
+    #[arg(short, long, value_name = "FILE")]
+    config: Option<String>,
+    /// Base seed; trial i draws from stream i of the generator seeded with N [default: random]
+    #[arg(short, long, value_name = "N", value_parser = seed)]
+    seed: Option<u64>,
+    /// Maximum number of generations, 0 for no limit [default: 1000]
+    #[arg(long, value_name = "N")]
+    generations: Option<usize>,
+    /// Stop after N generations without improvement, 0 for no limit [default: 100]
+    #[arg(long, value_name = "N")]
+    max_stagnant: Option<usize>,
+    /// Stop each trial after SECS seconds of wall-clock time
+    #[arg(long, value_name = "SECS", value_parser = seconds)]
+    time_limit: Option<f64>,
+    /// Stop after evaluating N chromosomes, the initial population included
+    #[arg(long, value_name = "N", value_parser = at_least_one)]
+    max_evaluations: Option<usize>,
+    /// Stop once the best weight is at most W (a known optimum or bound)
+    #[arg(long, value_name = "W")]
+    target_fitness: Option<usize>,
+    /// Stop once the population diversity in [0, 1] is at most D
+    #[arg(long, value_name = "D", value_parser = probability)]
+    min_diversity: Option<f64>,
+    /// Combine the stopping criteria; with all, --generations and --time-limit still stop the
+    /// run [default: any]
+    #[arg(long, value_name = "RULE")]
+    stop_when: Option<StopRule>,
+    /// Population size, 0 to derive it from the graph order [default: 50]
+    #[arg(long, value_name = "N")]
+    pop_size: Option<usize>,
+    /// Contestants in each selection tournament [default: 5]
+    #[arg(long, value_name = "K", value_parser = at_least_one)]
+    tournament_size: Option<usize>,
+    /// Draw distinct contestants in each selection tournament
+    #[arg(long)]
+    tournament_without_replacement: bool,
+    /// Turn off the draw without replacement enabled by --config
+    #[arg(long, conflicts_with = "tournament_without_replacement")]
+    tournament_with_replacement: bool,
+    /// Rank selection pressure in [1, 2] [default: 1.5]
+    #[arg(long, value_name = "SP", value_parser = selection_pressure)]
+    selection_pressure: Option<f64>,
+    /// Crossover probability in [0, 1] [default: 0.9]
+    #[arg(long, value_name = "P", value_parser = probability)]
+    crossover_rate: Option<f64>,
+    /// Mutation probability in [0, 1] [default: 0.1]
+    #[arg(long, value_name = "P", value_parser = probability)]
+    mutation_rate: Option<f64>,
+    /// Best chromosomes kept unchanged in each generation [default: 0]
+    #[arg(long, value_name = "N")]
+    elitism: Option<usize>,
+    /// Apply the redundancy-removal local search (memetic variant)
+    #[arg(long)]
+    local_search: bool,
+    /// Turn off the local search enabled by --config
+    #[arg(long, conflicts_with = "local_search")]
+    no_local_search: bool,
+    /// Comma-separated initial population heuristics [default: h1,h2,h3,h4,h5,h1]
+    #[arg(long, value_name = "LIST", value_delimiter = ',', value_parser = heuristic)]
+    heuristics: Option<Vec<HeuristicKind>>,
+    /// Crossover operator [default: single-point]
+    #[arg(long, value_name = "NAME")]
+    crossover: Option<CrossoverKind>,
+    /// Selection operator [default: tournament]
+    #[arg(long, value_name = "NAME")]
+    selection: Option<SelectionKind>,
+    /// Mutation operator [default: random-relabel]
+    #[arg(long, value_name = "NAME")]
+    mutation: Option<MutationKind>,
+}
+
+impl GaOptions {
+    /// Resolves the experiment settings: defaults, then `--config`, then the other options.
+    fn experiment(
+        &self,
+        trials: Option<usize>,
+        trial: Option<usize>,
+        default_trials: usize,
+    ) -> Result<ExperimentConfig, String> {
+        let defaults = ExperimentConfig {
+            trials: default_trials,
+            ..ExperimentConfig::default()
+        };
+        let base = match &self.config {
+            Some(path) => ExperimentConfig::load(path, defaults)?,
+            None => defaults,
+        };
+        let defaults = &base.params;
+
+        let params = AlgorithmParams {
+            max_stagnant: self.max_stagnant.unwrap_or(defaults.max_stagnant),
+            generations: self.generations.unwrap_or(defaults.generations),
+            tournament_size: self.tournament_size.unwrap_or(defaults.tournament_size),
+            tournament_without_replacement: (defaults.tournament_without_replacement
+                || self.tournament_without_replacement)
+                && !self.tournament_with_replacement,
+            selection_pressure: self
+                .selection_pressure
+                .unwrap_or(defaults.selection_pressure),
+            crossover_rate: self.crossover_rate.unwrap_or(defaults.crossover_rate),
+            pop_size: self.pop_size.unwrap_or(defaults.pop_size),
+            mutation_rate: self.mutation_rate.unwrap_or(defaults.mutation_rate),
+            elitism: self.elitism.unwrap_or(defaults.elitism),
+            local_search: (defaults.local_search || self.local_search) && !self.no_local_search,
+            time_limit: self.time_limit.or(defaults.time_limit),
+            max_evaluations: self.max_evaluations.or(defaults.max_evaluations),
+            target_fitness: self.target_fitness.or(defaults.target_fitness),
+            min_diversity: self.min_diversity.or(defaults.min_diversity),
+            stop_when: self.stop_when.unwrap_or(defaults.stop_when),
+        };
+
+        if !params.has_stopping_criterion() {
+            return Err(
+                "no stopping criterion is left: give '--generations', '--max-stagnant' or \
+                 another limit a non-zero value"
+                    .to_string(),
+            );
+        }
+        if params.stop_when == StopRule::All && !params.has_hard_limit() {
+            return Err(
+                "'--stop-when all' needs '--generations' or '--time-limit' to stop runs whose \
+                 other criteria are never met"
+                    .to_string(),
+            );
+        }
+        let trials = trials.unwrap_or(base.trials);
+        let trial = trial.or(base.trial);
+        if let Some(trial) = trial.filter(|&trial| trial > trials) {
+            return Err(format!(
+                "invalid value '{trial}' for '--trial': must be at most '--trials' ({trials})"
+            ));
+        }
+        if params.pop_size != 0 && params.elitism >= params.pop_size {
+            return Err(format!(
+                "invalid value '{}' for '--elitism': must be smaller than '--pop-size' ({})",
+                params.elitism, params.pop_size
+            ));
+        }
+
+        Ok(ExperimentConfig {
+            trials,
+            trial,
+            seed: self.seed.or(base.seed),
+            params,
+            heuristics: self.heuristics.clone().unwrap_or(base.heuristics),
+            crossover: self.crossover.unwrap_or(base.crossover),
+            selection: self.selection.unwrap_or(base.selection),
+            mutation: self.mutation.unwrap_or(base.mutation),
+        })
+    }
+}
+
+fn existing_file(path: &str) -> Result<String, String> {
+    if Path::new(path).is_file() {
+        Ok(path.to_string())
+    } else {
+        Err("file not found".to_string())
+    }
+}
+
+/// Trims one item of a comma-separated list; empty items are dropped by [`parse`].
+fn trimmed(item: &str) -> Result<String, String> {
+    Ok(item.trim().to_string())
+}
+
+fn at_least_one(raw: &str) -> Result<usize, String> {
+    match raw.parse() {
+        Ok(value) if value >= 1 => Ok(value),
+        _ => Err("expected an integer >= 1".to_string()),
+    }
+}
+
+fn seed(raw: &str) -> Result<u64, String> {
+    match raw.parse() {
+        Ok(value) if value <= MAX_SEED => Ok(value),
+        _ => Err(format!("expected an integer between 0 and {MAX_SEED}")),
+    }
+}
+
+fn probability(raw: &str) -> Result<f64, String> {
+    match raw.parse() {
+        Ok(value) if (0.0..=1.0).contains(&value) => Ok(value),
+        _ => Err("expected a number between 0 and 1".to_string()),
+    }
+}
+
+fn selection_pressure(raw: &str) -> Result<f64, String> {
+    match raw.parse() {
+        Ok(value) if (1.0..=2.0).contains(&value) => Ok(value),
+        _ => Err("expected a number between 1 and 2".to_string()),
+    }
+}
+
+fn seconds(raw: &str) -> Result<f64, String> {
+    match raw.parse::<f64>() {
+        Ok(value) if value > 0.0 && Duration::try_from_secs_f64(value).is_ok() => Ok(value),
+        _ => Err("expected a positive number of seconds below 2^64".to_string()),
+    }
+}
+
+fn heuristic(raw: &str) -> Result<HeuristicKind, String> {
+    HeuristicKind::from_str(raw.trim(), false).map_err(|_| {
+        let names: Vec<&str> = HeuristicKind::value_variants()
+            .iter()
+            .map(|kind| kind.name())
+            .collect();
+        format!(
+            "unknown heuristic '{raw}', expected a list of {}",
+            names.join(", ")
+        )
+    })
+}
+
+/// Parses the command line arguments, the program name first, and resolves the experiment
+/// settings of the commands that run the genetic algorithm.
+///
+/// # Errors
+///
+/// Returns the clap error to print, which is also how `--help` is reported.
+pub fn parse<I, T>(args: I) -> Result<Command, clap::Error>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    let invalid = |message: String| Cli::command().error(ErrorKind::ValueValidation, message);
+
+    let mut command = Cli::try_parse_from(args)?.command;
+    match &mut command {
+        Command::Solve(args) => {
+            args.config = args
+                .ga
+                .experiment(args.trials, args.trial, 1)
+                .map_err(invalid)?;
+        }
+        Command::Batch(args) => {
+            args.instances.retain(|path| !path.is_empty());
+            args.config = args.ga.experiment(args.trials, None, 1).map_err(invalid)?;
+        }
+        Command::Bench(args) => {
+            args.config = args.ga.experiment(args.trials, None, 5).map_err(invalid)?;
+        }
+        Command::Report(args) => args.results.retain(|path| !path.is_empty()),
+        Command::Validate(_) | Command::Exact(_) | Command::Info(_) => {}
+    }
+    Ok(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BIN: &str = env!("CARGO_PKG_NAME");
+
+    /// Writes `content` to a file in the temporary directory and returns its path.
+    fn scratch(name: &str, content: &str) -> String {
+        let path = std::env::temp_dir().join(format!("{BIN}-cli-{}-{name}", std::process::id()));
+        std::fs::write(&path, content).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    fn graph() -> String {
+        scratch("graph.txt", "1 2\n2 3\n")
+    }
+
+    fn run(args: &[&str]) -> Result<Command, clap::Error> {
+        parse(std::iter::once(BIN).chain(args.iter().copied()))
+    }
+
+    fn error(args: &[&str]) -> clap::Error {
+        match run(args) {
+            Ok(command) => panic!("{args:?} was accepted as {command:?}"),
+            Err(e) => e,
+        }
+    }
+
+    fn solve(args: &[&str]) -> SolveArgs {
+        let graph = graph();
+        let args: Vec<&str> = ["solve", "-g", &graph]
+            .iter()
+            .chain(args)
+            .copied()
+            .collect();
+        match run(&args) {
+            Ok(Command::Solve(args)) => args,
+            other => panic!("{other:?}"),
+        }
+    }
+
+    #[test]
+    fn command_definition_is_consistent() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn every_command_is_parsed() {
+        let graph = graph();
+        let labeling = scratch("labeling.sol", "1 2\n2 2\n");
+
+        match run(&[
+            "solve", "--graph", &graph, "-n", "3", "-o", "out.csv", "--resume",
+        ]) {
+            Ok(Command::Solve(args)) => {
+                assert_eq!(args.graph, graph);
+                assert_eq!(args.config.trials, 3);
+                assert_eq!(args.output.as_deref(), Some("out.csv"));
+                assert!(args.resume);
+                assert_eq!(args.checkpoint_every, 100);
+            }
+            other => panic!("{other:?}"),
+        }
+        match run(&["batch", "-i", "a.txt, b.txt,", "-o", "out", "--threads=4"]) {
+            Ok(Command::Batch(args)) => {
+                assert_eq!(args.instances, ["a.txt", "b.txt"]);
+                assert_eq!(args.output_dir, "out");
+                assert_eq!(args.threads, 4);
+                assert_eq!(args.config.trials, 1);
+            }
+            other => panic!("{other:?}"),
+        }
+        match run(&["bench", "-g", &graph, "--drop-isolated"]) {
+            Ok(Command::Bench(args)) => {
+                assert_eq!(args.config.trials, 5);
+                assert!(args.drop_isolated);
+            }
+            other => panic!("{other:?}"),
+        }
+        match run(&["report", "-r", "a.csv,results", "-f", "latex"]) {
+            Ok(Command::Report(args)) => {
+                assert_eq!(args.results, ["a.csv", "results"]);
+                assert_eq!(args.format, ReportFormat::Latex);
+                assert_eq!(args.optima, None);
+            }
+            other => panic!("{other:?}"),
+        }
+        match run(&["validate", "-g", &graph, "-l", &labeling]) {
+            Ok(Command::Validate(args)) => assert_eq!(args.labeling, labeling),
+            other => panic!("{other:?}"),
+        }
+        match run(&["exact", "-g", &graph, "--node-limit", "10"]) {
+            Ok(Command::Exact(args)) => {
+                assert_eq!(args.node_limit, Some(10));
+                assert_eq!(args.solution, None);
+            }
+            other => panic!("{other:?}"),
+        }
+        match run(&["info", "-g", &graph]) {
+            Ok(Command::Info(args)) => assert_eq!(args.graph, graph),
+            other => panic!("{other:?}"),
+        }
+    }
+
+    #[test]
+    fn required_files_and_dependent_options_are_checked() {
+        let graph = graph();
+        for (args, kind) in [
+            (
+                &["solve", "-n", "2"][..],
+                ErrorKind::MissingRequiredArgument,
+            ),
+            (
+                &["batch", "-i", "a.txt"],
+                ErrorKind::MissingRequiredArgument,
+            ),
+            (
+                &["validate", "-g", &graph, "-l", "missing.sol"],
+                ErrorKind::ValueValidation,
+            ),
+            (
+                &["info", "-g", &graph, "--seed", "1"],
+                ErrorKind::UnknownArgument,
+            ),
+            (
+                &["solve", "-g", &graph, "--resume"],
+                ErrorKind::MissingRequiredArgument,
+            ),
+            (
+                &["solve", "-g", &graph, "--checkpoint-every", "5"],
+                ErrorKind::MissingRequiredArgument,
+            ),
+            (
+                &["solve", "-g", &graph, "--restore", &graph, "-n", "2"],
+                ErrorKind::ArgumentConflict,
+            ),
+            (
+                &["solve", "-g", &graph, "--restore", &graph, "--trial", "2"],
+                ErrorKind::ArgumentConflict,
+            ),
+            (
+                &["solve", "-g", &graph, "--local-search", "--no-local-search"],
+                ErrorKind::ArgumentConflict,
+            ),
+            (
+                &[
+                    "solve",
+                    "-g",
+                    &graph,
+                    "--tournament-without-replacement",
+                    "--tournament-with-replacement",
+                ],
+                ErrorKind::ArgumentConflict,
+            ),
+        ] {
+            assert_eq!(error(args).kind(), kind, "{args:?}");
+        }
+    }
+
+    #[test]
+    fn values_out_of_range_are_refused() {
+        let graph = graph();
+        for (option, value, expected) in [
+            ("--trials", "0", "an integer >= 1"),
+            ("--trial", "0", "an integer >= 1"),
+            ("--tournament-size", "0", "an integer >= 1"),
+            ("--crossover-rate", "1.5", "a number between 0 and 1"),
+            ("--mutation-rate", "-0.1", "a number between 0 and 1"),
+            ("--min-diversity", "2", "a number between 0 and 1"),
+            ("--selection-pressure", "2.5", "a number between 1 and 2"),
+            (
+                "--time-limit",
+                "0",
+                "a positive number of seconds below 2^64",
+            ),
+            (
+                "--time-limit",
+                "1e30",
+                "a positive number of seconds below 2^64",
+            ),
+            ("--max-evaluations", "0", "an integer >= 1"),
+            (
+                "--seed",
+                "x",
+                "an integer between 0 and 9223372036854775807",
+            ),
+            (
+                "--seed",
+                "9223372036854775808",
+                "an integer between 0 and 9223372036854775807",
+            ),
+            ("--heuristics", "h1,h9", "unknown heuristic 'h9'"),
+        ] {
+            let err = error(&["solve", "-g", &graph, option, value]);
+            assert_eq!(err.kind(), ErrorKind::ValueValidation, "{option} {value}");
+            assert!(
+                err.to_string().contains(expected),
+                "{option} {value}: {err}"
+            );
+        }
+        assert_eq!(
+            error(&["solve", "-g", &graph, "--selection", "best"]).kind(),
+            ErrorKind::InvalidValue
+        );
+    }
+
+    #[test]
+    fn resolved_settings_are_checked() {
+        let graph = graph();
+        for (args, message) in [
+            (
+                &["--pop-size", "10", "--elitism", "10"][..],
+                "invalid value '10' for '--elitism': must be smaller than '--pop-size' (10)",
+            ),
+            (
+                &["--generations", "0", "--max-stagnant", "0"],
+                "no stopping criterion is left",
+            ),
+            (
+                &[
+                    "--stop-when",
+                    "all",
+                    "--generations",
+                    "0",
+                    "--target-fitness",
+                    "3",
+                ],
+                "'--stop-when all' needs '--generations' or '--time-limit'",
+            ),
+            (
+                &["-n", "3", "--trial", "4"],
+                "invalid value '4' for '--trial': must be at most '--trials' (3)",
+            ),
+        ] {
+            let args: Vec<&str> = ["solve", "-g", &graph]
+                .iter()
+                .chain(args)
+                .copied()
+                .collect();
+            let err = error(&args);
+            assert_eq!(err.kind(), ErrorKind::ValueValidation, "{args:?}");
+            assert!(err.to_string().contains(message), "{err}");
+        }
+
+        let args = solve(&[
+            "--stop-when",
+            "all",
+            "--generations",
+            "0",
+            "--time-limit",
+            "5",
+        ]);
+        assert_eq!(args.config.params.stop_when, StopRule::All);
+    }
+
+    #[test]
+    fn named_values_are_read() {
+        let args = solve(&["--crossover", "uniform", "--heuristics", "h2, h5"]);
+        assert_eq!(args.config.crossover, CrossoverKind::Uniform);
+        assert_eq!(
+            args.config.heuristics,
+            [HeuristicKind::H2, HeuristicKind::H5]
+        );
+    }
+
+    #[test]
+    fn options_override_the_config_file() {
+        let config = scratch(
+            "config.toml",
+            "trials = 7\nseed = 3\n\n\
+             [algorithm]\ngenerations = 500\ncrossover_rate = 0.7\nlocal_search = true\n\n\
+             [operators]\nselection = \"rank\"\n",
+        );
+
+        let args = solve(&["-c", &config]);
+        assert_eq!(args.config.trials, 7);
+        assert_eq!(args.config.seed, Some(3));
+        assert_eq!(args.config.params.generations, 500);
+        assert_eq!(args.config.params.crossover_rate, 0.7);
+        assert!(args.config.params.local_search);
+        assert_eq!(args.config.selection, SelectionKind::Rank);
+        assert_eq!(args.config.params.mutation_rate, 0.1);
+        assert_eq!(args.config.trial, None);
+        assert_eq!(
+            solve(&["-c", &config, "--trial", "7"]).config.trial,
+            Some(7)
+        );
+
+        let args = solve(&[
+            "--seed",
+            "9",
+            "--crossover-rate",
+            "0.5",
+            "--no-local-search",
+            "-n",
+            "2",
+            "--config",
+            &config,
+            "--selection=roulette",
+        ]);
+        assert_eq!(args.config.trials, 2);
+        assert_eq!(args.config.seed, Some(9));
+        assert_eq!(args.config.params.generations, 500);
+        assert_eq!(args.config.params.crossover_rate, 0.5);
+        assert!(!args.config.params.local_search);
+        assert_eq!(args.config.selection, SelectionKind::Roulette);
+
+        let config = scratch(
+            "tournament.toml",
+            "[algorithm]\ntournament_without_replacement = true\n",
+        );
+        assert!(
+            solve(&["-c", &config])
+                .config
+                .params
+                .tournament_without_replacement
+        );
+        assert!(
+            !solve(&["-c", &config, "--tournament-with-replacement"])
+                .config
+                .params
+                .tournament_without_replacement
+        );
+        assert!(
+            solve(&["--tournament-without-replacement"])
+                .config
+                .params
+                .tournament_without_replacement
+        );
+
+        let invalid = scratch("invalid.toml", "[algorithm]\ncrossover_rate = 2\n");
+        let graph = graph();
+        assert!(error(&["solve", "-g", &graph, "-c", &invalid])
+            .to_string()
+            .contains("crossover_rate"));
+    }
+}
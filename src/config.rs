@@ -0,0 +1,628 @@
+use std::{fs, ops::RangeInclusive, path::Path, time::Duration};
+
+use clap::{builder::PossibleValue, ValueEnum};
+use serde::{Deserialize, Serialize};
+
+/// Genetic algorithm parameters shared by `solve` and `bench`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlgorithmParams {
+    /// Generations without improvement before stopping, `0` for no limit.
+    pub max_stagnant: usize,
+    /// Maximum number of generations, `0` for no limit.
+    pub generations: usize,
+    pub tournament_size: usize,
+    pub tournament_without_replacement: bool,
+    pub selection_pressure: f64,
+    pub crossover_rate: f64,
+    pub pop_size: usize,
+    pub mutation_rate: f64,
+    pub elitism: usize,
+    pub local_search: bool,
+    /// Wall-clock limit of each trial, in seconds.
+    pub time_limit: Option<f64>,
+    pub max_evaluations: Option<usize>,
+    /// Stop once the best weight is at most this value (a known optimum or bound).
+    pub target_fitness: Option<usize>,
+    /// Stop once `Population::diversity` is at most this value.
+    pub min_diversity: Option<f64>,
+    /// How the termination criteria combine.
+    pub stop_when: StopRule,
+}
+
+impl Default for AlgorithmParams {
+    fn default() -> Self {
+        Self {
+            max_stagnant: 100,
+            generations: 1000,
+            tournament_size: 5,
+            tournament_without_replacement: false,
+            selection_pressure: 1.5,
+            crossover_rate: 0.9,
+            pop_size: 50,
+            mutation_rate: 0.1,
+            elitism: 0,
+            local_search: false,
+            time_limit: None,
+            max_evaluations: None,
+            target_fitness: None,
+            min_diversity: None,
+            stop_when: StopRule::Any,
+        }
+    }
+}
+
+impl AlgorithmParams {
+    /// Tells whether any termination criterion is set, without which a run would never stop.
+    pub fn has_stopping_criterion(&self) -> bool {
+        self.generations > 0
+            || self.max_stagnant > 0
+            || self.time_limit.is_some()
+            || self.max_evaluations.is_some()
+            || self.target_fitness.is_some()
+            || self.min_diversity.is_some()
+    }
+
+    /// Tells whether a generation or time limit is set. With [`StopRule::All`] these two are
+    /// hard caps, which stop a run even when the other criteria are never met.
+    pub fn has_hard_limit(&self) -> bool {
+        self.generations > 0 || self.time_limit.is_some()
+    }
+}
+
+/// Declares an operator enum whose variants are selected by name in configs and on the CLI.
+macro_rules! named_kind {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident => $label:literal),+ $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+        pub enum $name {
+            $(#[serde(rename = $label)] $variant),+
+        }
+
+        impl $name {
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $label),+
+                }
+            }
+        }
+
+        impl ValueEnum for $name {
+            fn value_variants<'a>() -> &'a [Self] {
+                &[$(Self::$variant),+]
+            }
+
+            fn to_possible_value(&self) -> Option<PossibleValue> {
+                Some(PossibleValue::new(self.name()))
+            }
+        }
+    };
+}
+
+named_kind!(
+    /// How the termination criteria combine: stop when any of them holds, or only when all do.
+    StopRule { Any => "any", All => "all" }
+);
+
+named_kind!(
+    /// Table formats of the `report` command.
+    ReportFormat { Csv => "csv", Markdown => "markdown", Latex => "latex" }
+);
+
+named_kind!(
+    /// Heuristics used to build the initial population.
+    HeuristicKind { H1 => "h1", H2 => "h2", H3 => "h3", H4 => "h4", H5 => "h5" }
+);
+
+named_kind!(
+    /// Crossover operators.
+    CrossoverKind {
+        SinglePoint => "single-point",
+        TwoPoint => "two-point",
+        Uniform => "uniform",
+        Neighborhood => "neighborhood",
+    }
+);
+
+named_kind!(
+    /// Selection operators.
+    SelectionKind {
+        Tournament => "tournament",
+        Roulette => "roulette",
+        Rank => "rank",
+        Sus => "sus",
+    }
+);
+
+named_kind!(
+    /// Mutation operators.
+    MutationKind {
+        RandomRelabel => "random-relabel",
+        SwapLabels => "swap",
+        DemoteTwo => "demote-two",
+    }
+);
+
+/// Largest accepted seed. The seed is echoed into TOML, whose integers are signed 64-bit.
+pub const MAX_SEED: u64 = i64::MAX as u64;
+
+/// Fully resolved experiment settings: defaults, then the config file, then CLI options.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExperimentConfig {
+    pub trials: usize,
+    /// Runs only this one of the `trials`, which gives the same row as in the full run.
+    pub trial: Option<usize>,
+    /// Base seed, at most [`MAX_SEED`]; trial `i` draws from stream `i` of the generator it
+    /// keys. Drawn at random when absent.
+    pub seed: Option<u64>,
+    pub params: AlgorithmParams,
+    pub heuristics: Vec<HeuristicKind>,
+    pub crossover: CrossoverKind,
+    pub selection: SelectionKind,
+    pub mutation: MutationKind,
+}
+
+impl Default for ExperimentConfig {
+    fn default() -> Self {
+        use HeuristicKind::{H1, H2, H3, H4, H5};
+        Self {
+            trials: 1,
+            trial: None,
+            seed: None,
+            params: AlgorithmParams::default(),
+            heuristics: vec![H1, H2, H3, H4, H5, H1],
+            crossover: CrossoverKind::SinglePoint,
+            selection: SelectionKind::Tournament,
+            mutation: MutationKind::RandomRelabel,
+        }
+    }
+}
+
+/// Layout of a config file. Every key may be left out, keeping its default, and unknown keys
+/// are refused.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    trials: Option<usize>,
+    trial: Option<usize>,
+    seed: Option<u64>,
+    #[serde(default)]
+    algorithm: AlgorithmTable,
+    #[serde(default)]
+    operators: OperatorsTable,
+}
+
+/// The `[algorithm]` table of a config file, named as the fields of [`AlgorithmParams`].
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct AlgorithmTable {
+    generations: Option<usize>,
+    max_stagnant: Option<usize>,
+    pop_size: Option<usize>,
+    tournament_size: Option<usize>,
+    tournament_without_replacement: Option<bool>,
+    selection_pressure: Option<f64>,
+    crossover_rate: Option<f64>,
+    mutation_rate: Option<f64>,
+    elitism: Option<usize>,
+    local_search: Option<bool>,
+    time_limit: Option<f64>,
+    max_evaluations: Option<usize>,
+    target_fitness: Option<usize>,
+    min_diversity: Option<f64>,
+    stop_when: Option<StopRule>,
+}
+
+/// The `[operators]` table of a config file.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct OperatorsTable {
+    heuristics: Option<Vec<HeuristicKind>>,
+    crossover: Option<CrossoverKind>,
+    selection: Option<SelectionKind>,
+    mutation: Option<MutationKind>,
+}
+
+impl ExperimentConfig {
+    /// Reads a `.toml` or `.json` file and applies it over `defaults`.
+    pub fn load(path: &str, defaults: Self) -> Result<Self, String> {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("failed to read '{path}': {e}"))?;
+        let is_json = Path::new(path)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+            || content.trim_start().starts_with('{');
+
+        let file = read(&content, is_json).map_err(|e| format!("{path}: {e}"))?;
+        let mut config = defaults;
+        config.apply(file).map_err(|e| format!("{path}: {e}"))?;
+        Ok(config)
+    }
+
+    /// Reads settings written by [`ExperimentConfig::to_toml`] and applies them over
+    /// `defaults`.
+    pub fn from_toml(content: &str, defaults: Self) -> Result<Self, String> {
+        let mut config = defaults;
+        config.apply(read(content, false)?)?;
+        Ok(config)
+    }
+
+    fn apply(&mut self, file: ConfigFile) -> Result<(), String> {
+        let ConfigFile {
+            trials,
+            trial,
+            seed,
+            algorithm,
+            operators,
+        } = file;
+        if let Some(trials) = trials {
+            self.trials = at_least("trials", trials, 1)?;
+        }
+        if let Some(trial) = trial {
+            self.trial = Some(at_least("trial", trial, 1)?);
+        }
+        if let Some(trial) = self.trial.filter(|&trial| trial > self.trials) {
+            return Err(format!(
+                "'trial' must be at most 'trials' ({}), found {trial}",
+                self.trials
+            ));
+        }
+        if let Some(seed) = seed {
+            self.seed = Some(at_most("seed", seed, MAX_SEED)?);
+        }
+
+        let params = &mut self.params;
+        params.generations = algorithm.generations.unwrap_or(params.generations);
+        params.max_stagnant = algorithm.max_stagnant.unwrap_or(params.max_stagnant);
+        params.pop_size = algorithm.pop_size.unwrap_or(params.pop_size);
+        if let Some(size) = algorithm.tournament_size {
+            params.tournament_size = at_least("algorithm.tournament_size", size, 1)?;
+        }
+        params.tournament_without_replacement = algorithm
+            .tournament_without_replacement
+            .unwrap_or(params.tournament_without_replacement);
+        if let Some(sp) = algorithm.selection_pressure {
+            params.selection_pressure = selection_pressure("algorithm.selection_pressure", sp)?;
+        }
+        if let Some(rate) = algorithm.crossover_rate {
+            params.crossover_rate = probability("algorithm.crossover_rate", rate)?;
+        }
+        if let Some(rate) = algorithm.mutation_rate {
+            params.mutation_rate = probability("algorithm.mutation_rate", rate)?;
+        }
+        params.elitism = algorithm.elitism.unwrap_or(params.elitism);
+        params.local_search = algorithm.local_search.unwrap_or(params.local_search);
+        if let Some(limit) = algorithm.time_limit {
+            params.time_limit = Some(seconds("algorithm.time_limit", limit)?);
+        }
+        if let Some(budget) = algorithm.max_evaluations {
+            params.max_evaluations = Some(at_least("algorithm.max_evaluations", budget, 1)?);
+        }
+        params.target_fitness = algorithm.target_fitness.or(params.target_fitness);
+        if let Some(diversity) = algorithm.min_diversity {
+            params.min_diversity = Some(probability("algorithm.min_diversity", diversity)?);
+        }
+        params.stop_when = algorithm.stop_when.unwrap_or(params.stop_when);
+
+        if let Some(heuristics) = operators.heuristics {
+            if heuristics.is_empty() {
+                return Err("'operators.heuristics' must list at least one heuristic".to_string());
+            }
+            self.heuristics = heuristics;
+        }
+        self.crossover = operators.crossover.unwrap_or(self.crossover);
+        self.selection = operators.selection.unwrap_or(self.selection);
+        self.mutation = operators.mutation.unwrap_or(self.mutation);
+
+        Ok(())
+    }
+
+    /// Returns the numbers, from `1`, of the trials to run: all of them, or only `trial`.
+    pub fn trial_numbers(&self) -> RangeInclusive<usize> {
+        match self.trial {
+            Some(trial) => trial..=trial,
+            None => 1..=self.trials,
+        }
+    }
+
+    /// Renders the resolved config as TOML that [`ExperimentConfig::load`] reads back.
+    pub fn to_toml(&self) -> String {
+        toml::to_string(&ConfigFile::from(self)).expect("config files always render as TOML")
+    }
+}
+
+impl From<&ExperimentConfig> for ConfigFile {
+    /// Sets every key, leaving out only the optional settings that are absent.
+    fn from(config: &ExperimentConfig) -> Self {
+        let p = &config.params;
+        Self {
+            trials: Some(config.trials),
+            trial: config.trial,
+            seed: config.seed,
+            algorithm: AlgorithmTable {
+                generations: Some(p.generations),
+                max_stagnant: Some(p.max_stagnant),
+                pop_size: Some(p.pop_size),
+                tournament_size: Some(p.tournament_size),
+                tournament_without_replacement: Some(p.tournament_without_replacement),
+                selection_pressure: Some(p.selection_pressure),
+                crossover_rate: Some(p.crossover_rate),
+                mutation_rate: Some(p.mutation_rate),
+                elitism: Some(p.elitism),
+                local_search: Some(p.local_search),
+                time_limit: p.time_limit,
+                max_evaluations: p.max_evaluations,
+                target_fitness: p.target_fitness,
+                min_diversity: p.min_diversity,
+                stop_when: Some(p.stop_when),
+            },
+            operators: OperatorsTable {
+                heuristics: Some(config.heuristics.clone()),
+                crossover: Some(config.crossover),
+                selection: Some(config.selection),
+                mutation: Some(config.mutation),
+            },
+        }
+    }
+}
+
+/// Parses a config document, as JSON or as TOML.
+fn read(content: &str, json: bool) -> Result<ConfigFile, String> {
+    if json {
+        serde_json::from_str(content).map_err(|e| e.to_string())
+    } else {
+        toml::from_str(content).map_err(|e| e.to_string())
+    }
+}
+
+fn at_least(key: &str, value: usize, min: usize) -> Result<usize, String> {
+    if value >= min {
+        Ok(value)
+    } else {
+        Err(format!(
+            "'{key}' must be an integer >= {min}, found {value}"
+        ))
+    }
+}
+
+fn at_most(key: &str, value: u64, max: u64) -> Result<u64, String> {
+    if value <= max {
+        Ok(value)
+    } else {
+        Err(format!(
+            "'{key}' must be an integer <= {max}, found {value}"
+        ))
+    }
+}
+
+fn probability(key: &str, value: f64) -> Result<f64, String> {
+    if (0.0..=1.0).contains(&value) {
+        Ok(value)
+    } else {
+        Err(format!(
+            "'{key}' must be a number between 0 and 1, found {value}"
+        ))
+    }
+}
+
+fn selection_pressure(key: &str, value: f64) -> Result<f64, String> {
+    if (1.0..=2.0).contains(&value) {
+        Ok(value)
+    } else {
+        Err(format!(
+            "'{key}' must be a number between 1 and 2, found {value}"
+        ))
+    }
+}
+
+fn seconds(key: &str, value: f64) -> Result<f64, String> {
+    if value > 0.0 && Duration::try_from_secs_f64(value).is_ok() {
+        Ok(value)
+    } else {
+        Err(format!(
+            "'{key}' must be a positive number of seconds below 2^64, found {value}"
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toml_reads_tables_and_scalars() {
+        let config = ExperimentConfig::from_toml(
+            "# experiment\n\
+             trials = 30\n\
+             seed = 1_000\n\
+             \n\
+             [algorithm]\n\
+             crossover_rate = 0.8 # comment\n\
+             local_search = true\n\
+             \n\
+             [operators]\n\
+             crossover = \"two-point\"\n",
+            ExperimentConfig::default(),
+        )
+        .unwrap();
+
+        assert_eq!(config.trials, 30);
+        assert_eq!(config.seed, Some(1000));
+        assert_eq!(config.params.crossover_rate, 0.8);
+        assert!(config.params.local_search);
+        assert_eq!(config.crossover, CrossoverKind::TwoPoint);
+    }
+
+    #[test]
+    fn toml_reads_arrays_over_several_lines() {
+        use HeuristicKind::{H1, H2, H3};
+
+        let config = ExperimentConfig::from_toml(
+            "[operators]\n\
+             heuristics = [\n    \"h1\", # greedy\n    \"h2\",\n    \"h3\",\n]\n\
+             mutation = \"swap\"\n",
+            ExperimentConfig::default(),
+        )
+        .unwrap();
+
+        assert_eq!(config.heuristics, [H1, H2, H3]);
+        assert_eq!(config.mutation, MutationKind::SwapLabels);
+    }
+
+    #[test]
+    fn malformed_documents_are_refused() {
+        for content in [
+            "trials = 1\n\ntrials = 2\n",
+            "trials = 1\nheuristics = [\"h1\",\n\"h2\"\n",
+            "[algorithm\n",
+            "trials 30\n",
+            "trials = 30 31\n",
+        ] {
+            assert!(read(content, false).is_err(), "{content}");
+        }
+
+        for content in [
+            r#"{"trials": 1, "trials": 2}"#,
+            r#"{"trials": 1"#,
+            r#"{"trials": 1} 2"#,
+            r#"["trials"]"#,
+        ] {
+            assert!(read(content, true).is_err(), "{content}");
+        }
+    }
+
+    #[test]
+    fn unknown_keys_and_names_are_refused() {
+        let err = read("[algorithm]\ngeneration = 10\n", false).unwrap_err();
+        assert!(err.contains("generation"), "{err}");
+        assert!(read("[mutation]\nrate = 0.1\n", false).is_err());
+        assert!(read(r#"{"operators": {"crossover": "three-point"}}"#, true).is_err());
+        assert!(read("[algorithm]\ngenerations = \"many\"\n", false).is_err());
+        assert!(read("[algorithm]\ngenerations = -1\n", false).is_err());
+    }
+
+    #[test]
+    fn out_of_range_values_name_their_key() {
+        let mut config = ExperimentConfig::default();
+        for (content, key) in [
+            ("trials = 0\n", "'trials'"),
+            (
+                "[algorithm]\ncrossover_rate = 1.5\n",
+                "'algorithm.crossover_rate'",
+            ),
+            (
+                "[algorithm]\nselection_pressure = 3\n",
+                "'algorithm.selection_pressure'",
+            ),
+            ("[operators]\nheuristics = []\n", "'operators.heuristics'"),
+            ("trial = 0\n", "'trial'"),
+            ("trials = 2\ntrial = 3\n", "'trial'"),
+        ] {
+            let err = config.apply(read(content, false).unwrap()).unwrap_err();
+            assert!(err.starts_with(key), "{err}");
+        }
+    }
+
+    #[test]
+    fn seeds_must_fit_a_toml_integer() {
+        let mut config = ExperimentConfig::default();
+        let err = config
+            .apply(read(&format!("{{\"seed\": {}}}", MAX_SEED + 1), true).unwrap())
+            .unwrap_err();
+        assert!(err.starts_with("'seed'"), "{err}");
+
+        config
+            .apply(read(&format!("seed = {MAX_SEED}\n"), false).unwrap())
+            .unwrap();
+        assert_eq!(config.seed, Some(MAX_SEED));
+        let read = ExperimentConfig::from_toml(&config.to_toml(), ExperimentConfig::default());
+        assert_eq!(read.unwrap().seed, Some(MAX_SEED));
+    }
+
+    #[test]
+    fn json_reads_nested_objects() {
+        let mut config = ExperimentConfig::default();
+        config
+            .apply(
+                read(
+                    r#"{
+                        "trials": 5,
+                        "seed": null,
+                        "algorithm": { "mutation_rate": 0.25, "stop_when": "all" },
+                        "operators": { "heuristics": ["h4", "h5"], "crossover": "two-point" }
+                    }"#,
+                    true,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(config.trials, 5);
+        assert_eq!(config.seed, None);
+        assert_eq!(config.params.mutation_rate, 0.25);
+        assert_eq!(config.params.stop_when, StopRule::All);
+        assert_eq!(config.heuristics, [HeuristicKind::H4, HeuristicKind::H5]);
+        assert_eq!(config.crossover, CrossoverKind::TwoPoint);
+    }
+
+    #[test]
+    fn resolved_config_reads_back() {
+        let config = ExperimentConfig {
+            trials: 7,
+            trial: Some(3),
+            seed: Some(42),
+            params: AlgorithmParams {
+                max_stagnant: 20,
+                generations: 300,
+                tournament_size: 3,
+                tournament_without_replacement: true,
+                selection_pressure: 1.25,
+                crossover_rate: 0.75,
+                pop_size: 80,
+                mutation_rate: 0.05,
+                elitism: 2,
+                local_search: true,
+                time_limit: Some(1.5),
+                max_evaluations: Some(10_000),
+                target_fitness: Some(12),
+                min_diversity: Some(0.2),
+                stop_when: StopRule::All,
+            },
+            heuristics: vec![HeuristicKind::H3, HeuristicKind::H1],
+            crossover: CrossoverKind::Uniform,
+            selection: SelectionKind::Sus,
+            mutation: MutationKind::DemoteTwo,
+        };
+
+        let read = ExperimentConfig::from_toml(&config.to_toml(), ExperimentConfig::default());
+        assert_eq!(read.unwrap(), config);
+    }
+
+    #[test]
+    fn time_limits_must_fit_a_duration() {
+        let mut config = ExperimentConfig::default();
+        for limit in ["0", "-1.0", "1e30", "nan"] {
+            let file = read(&format!("[algorithm]\ntime_limit = {limit}\n"), false);
+            assert!(file.and_then(|file| config.apply(file)).is_err(), "{limit}");
+        }
+
+        config
+            .apply(read("[algorithm]\ntime_limit = 2\n", false).unwrap())
+            .unwrap();
+        assert_eq!(config.params.time_limit, Some(2.0));
+    }
+
+    #[test]
+    fn unset_keys_keep_the_given_defaults() {
+        let defaults = ExperimentConfig {
+            trials: 5,
+            seed: Some(3),
+            ..ExperimentConfig::default()
+        };
+        let config =
+            ExperimentConfig::from_toml("[algorithm]\ngenerations = 10\n", defaults).unwrap();
+
+        assert_eq!(config.trials, 5);
+        assert_eq!(config.seed, Some(3));
+        assert_eq!(config.params.generations, 10);
+    }
+}
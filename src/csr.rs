@@ -0,0 +1,392 @@
+use kambo_graph::{graphs::simple::UndirectedGraph, Graph};
+
+/// Immutable adjacency of a graph in compressed sparse row (CSR) form.
+///
+/// The neighbors of vertex `v` are `targets[offsets[v]..offsets[v + 1]]`, sorted. Built once
+/// per graph, it is shared by reference by every chromosome and operator, so no adjacency is
+/// copied while the algorithm runs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CsrGraph {
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+}
+
+impl CsrGraph {
+    /// Builds the adjacency of `graph`.
+    ///
+    /// # Parameters
+    /// - `graph: &UndirectedGraph<usize>`: A graph with vertices `0..n`, as produced by the
+    ///   loaders in [`crate::utils`].
+    ///
+    /// # Panics
+    /// - If a vertex or neighbor of `graph` is not in `0..n`.
+    #[must_use]
+    pub fn new(graph: &UndirectedGraph<usize>) -> Self {
+        let order = graph.order();
+        let mut offsets = Vec::with_capacity(order + 1);
+        let mut targets = Vec::with_capacity(2 * graph.edge_count());
+
+        offsets.push(0);
+        for v in 0..order {
+            let start = targets.len();
+            targets.extend(graph.neighbors(&v).into_iter().flatten().copied());
+            targets[start..].sort_unstable();
+            assert!(
+                targets[start..].iter().all(|&n| n < order),
+                "Vertex {v} has a neighbor outside 0..{order}"
+            );
+            offsets.push(targets.len());
+        }
+
+        Self { offsets, targets }
+    }
+
+    /// Returns the number of vertices.
+    #[inline]
+    #[must_use]
+    pub fn order(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Returns the number of edges.
+    #[inline]
+    #[must_use]
+    pub fn edge_count(&self) -> usize {
+        self.targets.len() / 2
+    }
+
+    /// Returns the neighbors of `vertex`, sorted.
+    ///
+    /// # Panics
+    /// - If `vertex` is not in `0..n`.
+    #[inline]
+    #[must_use]
+    pub fn neighbors(&self, vertex: usize) -> &[usize] {
+        &self.targets[self.offsets[vertex]..self.offsets[vertex + 1]]
+    }
+
+    /// Returns the degree of `vertex`.
+    ///
+    /// # Panics
+    /// - If `vertex` is not in `0..n`.
+    #[inline]
+    #[must_use]
+    pub fn degree(&self, vertex: usize) -> usize {
+        self.offsets[vertex + 1] - self.offsets[vertex]
+    }
+}
+
+/// Sentinel for "no vertex" in the bucket lists.
+const NONE: usize = usize::MAX;
+
+/// Shrinking subgraph of a [`CsrGraph`] with its vertices bucketed by current degree.
+///
+/// Used by the constructive heuristics, which repeatedly pick a vertex (at random or of
+/// maximum degree) and delete it together with its neighbors. Each bucket is a doubly linked
+/// list, so removing a vertex costs `O(deg)` and finding a vertex of maximum degree is
+/// amortized `O(1)`, instead of cloning the graph and scanning every remaining vertex.
+#[derive(Clone, Debug)]
+pub struct DegreeBuckets<'a> {
+    graph: &'a CsrGraph,
+    degree: Vec<usize>,
+    removed: Vec<bool>,
+    head: Vec<usize>,
+    next: Vec<usize>,
+    prev: Vec<usize>,
+    max_degree: usize,
+    remaining: Vec<usize>,
+    position: Vec<usize>,
+}
+
+impl<'a> DegreeBuckets<'a> {
+    /// Starts from the whole graph.
+    #[must_use]
+    pub fn new(graph: &'a CsrGraph) -> Self {
+        let order = graph.order();
+        let degree: Vec<usize> = (0..order).map(|v| graph.degree(v)).collect();
+        let max_degree = degree.iter().copied().max().unwrap_or(0);
+
+        let mut buckets = Self {
+            graph,
+            degree,
+            removed: vec![false; order],
+            head: vec![NONE; max_degree + 1],
+            next: vec![NONE; order],
+            prev: vec![NONE; order],
+            max_degree,
+            remaining: (0..order).collect(),
+            position: (0..order).collect(),
+        };
+        for v in (0..order).rev() {
+            buckets.link(v);
+        }
+
+        buckets
+    }
+
+    fn link(&mut self, v: usize) {
+        let d = self.degree[v];
+        self.prev[v] = NONE;
+        self.next[v] = self.head[d];
+        if self.head[d] != NONE {
+            self.prev[self.head[d]] = v;
+        }
+        self.head[d] = v;
+    }
+
+    fn unlink(&mut self, v: usize) {
+        let (prev, next) = (self.prev[v], self.next[v]);
+        if prev == NONE {
+            self.head[self.degree[v]] = next;
+        } else {
+            self.next[prev] = next;
+        }
+        if next != NONE {
+            self.prev[next] = prev;
+        }
+    }
+
+    /// Returns `true` if every vertex has been removed.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.remaining.is_empty()
+    }
+
+    /// Returns the number of remaining vertices.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.remaining.len()
+    }
+
+    /// Returns `true` if `vertex` has not been removed.
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, vertex: usize) -> bool {
+        !self.removed[vertex]
+    }
+
+    /// Returns the remaining vertices, in no particular order.
+    #[inline]
+    #[must_use]
+    pub fn vertices(&self) -> &[usize] {
+        &self.remaining
+    }
+
+    /// Returns the degree of `vertex` in the remaining subgraph.
+    #[inline]
+    #[must_use]
+    pub fn degree(&self, vertex: usize) -> usize {
+        self.degree[vertex]
+    }
+
+    /// Returns the remaining neighbors of `vertex`, sorted.
+    pub fn neighbors(&self, vertex: usize) -> impl Iterator<Item = usize> + '_ {
+        self.graph
+            .neighbors(vertex)
+            .iter()
+            .copied()
+            .filter(|&n| !self.removed[n])
+    }
+
+    /// Returns a remaining vertex of maximum degree, if any vertex is left.
+    #[must_use]
+    pub fn max_degree_vertex(&mut self) -> Option<usize> {
+        while self.max_degree > 0 && self.head[self.max_degree] == NONE {
+            self.max_degree -= 1;
+        }
+        let v = self.head[self.max_degree];
+        (v != NONE).then_some(v)
+    }
+
+    /// Returns the remaining vertices of degree `0`, sorted.
+    #[must_use]
+    pub fn isolated(&self) -> Vec<usize> {
+        let mut isolated = Vec::new();
+        let mut v = self.head.first().copied().unwrap_or(NONE);
+        while v != NONE {
+            isolated.push(v);
+            v = self.next[v];
+        }
+        isolated.sort_unstable();
+        isolated
+    }
+
+    /// Removes `vertex` and its edges, lowering the degree of its remaining neighbors.
+    ///
+    /// Removing a vertex twice has no effect.
+    pub fn remove(&mut self, vertex: usize) {
+        if self.removed[vertex] {
+            return;
+        }
+
+        self.unlink(vertex);
+        self.removed[vertex] = true;
+
+        let idx = self.position[vertex];
+        self.remaining.swap_remove(idx);
+        if let Some(&moved) = self.remaining.get(idx) {
+            self.position[moved] = idx;
+        }
+
+        for &n in self.graph.neighbors(vertex) {
+            if !self.removed[n] {
+                self.unlink(n);
+                self.degree[n] -= 1;
+                self.link(n);
+            }
+        }
+    }
+}
+
+impl From<&UndirectedGraph<usize>> for CsrGraph {
+    fn from(graph: &UndirectedGraph<usize>) -> Self {
+        Self::new(graph)
+    }
+}
+
+/// Graphs shared by the unit tests of the crate.
+#[cfg(test)]
+pub(crate) mod test_graphs {
+    use kambo_graph::{graphs::simple::UndirectedGraph, GraphMut};
+    use rand::Rng;
+
+    /// Path `0-1-...-(order - 1)`.
+    pub(crate) fn path(order: usize) -> UndirectedGraph<usize> {
+        let mut graph = UndirectedGraph::<usize>::new_undirected();
+        for v in 0..order {
+            graph.add_vertex(v).unwrap();
+        }
+        for v in 1..order {
+            graph.add_edge(&(v - 1), &v).unwrap();
+        }
+        graph
+    }
+
+    /// Random graph around a Hamiltonian path, so no vertex is isolated: every other edge is
+    /// present with probability `density`.
+    pub(crate) fn random_connected(
+        order: usize,
+        density: f64,
+        rng: &mut impl Rng,
+    ) -> UndirectedGraph<usize> {
+        let mut graph = path(order);
+        for u in 0..order {
+            for v in u + 2..order {
+                if rng.gen_bool(density) {
+                    graph.add_edge(&u, &v).unwrap();
+                }
+            }
+        }
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use kambo_graph::GraphMut;
+
+    use super::*;
+
+    /// Path 0-1-2-3-4 plus the chord 1-3, the pendant 3-5 and the isolated vertex 6.
+    fn sample() -> CsrGraph {
+        let mut graph = UndirectedGraph::<usize>::new_undirected();
+        for v in 0..7 {
+            graph.add_vertex(v).unwrap();
+        }
+        for (u, v) in [(0, 1), (1, 2), (2, 3), (3, 4), (1, 3), (3, 5)] {
+            graph.add_edge(&u, &v).unwrap();
+        }
+        CsrGraph::new(&graph)
+    }
+
+    /// Checks every bucket invariant against a recount over the remaining vertices.
+    fn assert_consistent(buckets: &mut DegreeBuckets, graph: &CsrGraph) {
+        let remaining: Vec<usize> = (0..graph.order())
+            .filter(|&v| buckets.contains(v))
+            .collect();
+        let mut vertices = buckets.vertices().to_vec();
+        vertices.sort_unstable();
+        assert_eq!(vertices, remaining);
+        assert_eq!(buckets.len(), remaining.len());
+        assert_eq!(buckets.is_empty(), remaining.is_empty());
+
+        for &v in &remaining {
+            let neighbors: Vec<usize> = buckets.neighbors(v).collect();
+            assert!(neighbors.iter().all(|&n| buckets.contains(n)));
+            assert_eq!(buckets.degree(v), neighbors.len(), "vertex {v}");
+        }
+
+        let isolated: Vec<usize> = remaining
+            .iter()
+            .copied()
+            .filter(|&v| buckets.degree(v) == 0)
+            .collect();
+        assert_eq!(buckets.isolated(), isolated);
+
+        let max_degree = remaining.iter().map(|&v| buckets.degree(v)).max();
+        let max_vertex = buckets.max_degree_vertex();
+        assert_eq!(max_vertex.map(|v| buckets.degree(v)), max_degree);
+        if let Some(v) = max_vertex {
+            assert!(buckets.contains(v));
+        }
+    }
+
+    #[test]
+    fn new_buckets_match_the_graph() {
+        let graph = sample();
+        let mut buckets = DegreeBuckets::new(&graph);
+
+        assert_eq!(buckets.len(), 7);
+        assert_eq!(buckets.degree(3), 4);
+        assert_eq!(buckets.max_degree_vertex(), Some(3));
+        assert_eq!(buckets.isolated(), [6]);
+        assert_consistent(&mut buckets, &graph);
+    }
+
+    #[test]
+    fn removal_lowers_the_degree_of_the_neighbors() {
+        let graph = sample();
+        let mut buckets = DegreeBuckets::new(&graph);
+
+        buckets.remove(3);
+        assert!(!buckets.contains(3));
+        assert_eq!([1, 2, 4, 5].map(|v| buckets.degree(v)), [2, 1, 0, 0]);
+        assert_eq!(buckets.max_degree_vertex(), Some(1));
+        assert_eq!(buckets.isolated(), [4, 5, 6]);
+        assert_consistent(&mut buckets, &graph);
+
+        buckets.remove(3);
+        assert_eq!(buckets.len(), 6);
+        assert_eq!(buckets.degree(1), 2);
+        assert_consistent(&mut buckets, &graph);
+
+        for v in [4, 5, 6, 1] {
+            buckets.remove(v);
+            assert_consistent(&mut buckets, &graph);
+        }
+        assert_eq!(buckets.isolated(), [0, 2]);
+        let last = buckets.max_degree_vertex().unwrap();
+        assert_eq!(buckets.degree(last), 0);
+
+        buckets.remove(0);
+        buckets.remove(2);
+        assert!(buckets.is_empty());
+        assert_eq!(buckets.max_degree_vertex(), None);
+        assert_eq!(buckets.isolated(), []);
+    }
+
+    #[test]
+    fn every_removal_order_keeps_the_buckets_consistent() {
+        let graph = sample();
+        for start in 0..7 {
+            let mut buckets = DegreeBuckets::new(&graph);
+            for step in 0..7 {
+                buckets.remove((start + 3 * step) % 7);
+                assert_consistent(&mut buckets, &graph);
+            }
+            assert!(buckets.is_empty());
+        }
+    }
+}
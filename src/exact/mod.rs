@@ -0,0 +1,11 @@
+/// Integer programming model of total Roman domination.
+pub mod model;
+
+/// Linear relaxation solver.
+mod simplex;
+
+/// Branch-and-bound search.
+pub mod solver;
+
+pub use model::{Constraint, Sense, TrdModel};
+pub use solver::{ExactError, ExactSolution, ExactSolver};
@@ -0,0 +1,134 @@
+use kambo_graph::{graphs::simple::UndirectedGraph, Graph};
+
+use crate::genetic::Chromosome;
+
+/// Direction of a linear constraint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sense {
+    /// `a·x <= b`
+    LessEqual,
+    /// `a·x >= b`
+    GreaterEqual,
+    /// `a·x = b`
+    Equal,
+}
+
+/// A sparse linear constraint `Σ a_j·x_j (sense) b`.
+#[derive(Clone, Debug)]
+pub struct Constraint {
+    /// Pairs `(variable index, coefficient)`.
+    pub coefficients: Vec<(usize, f64)>,
+    /// Direction of the constraint.
+    pub sense: Sense,
+    /// Right-hand side `b`.
+    pub rhs: f64,
+}
+
+/// 0/1 integer program for the total Roman domination problem.
+///
+/// For a graph with `n` vertices the model has `2n` binary variables:
+/// - `x_v` (index `v`): `1` if `f(v) = 1`.
+/// - `y_v` (index `n + v`): `1` if `f(v) = 2`.
+///
+/// It minimizes `Σ x_v + 2·y_v` subject to, for every vertex `v`:
+/// - `x_v + y_v <= 1` (a vertex has a single label);
+/// - `x_v + y_v + Σ_{u ∈ N(v)} y_u >= 1` (a vertex labeled `0` has a neighbor labeled `2`);
+/// - `Σ_{u ∈ N(v)} (x_u + y_u) - x_v - y_v >= 0` (a labeled vertex has a labeled neighbor).
+///
+/// The first family already bounds every variable by `1`, so the LP relaxation does not
+/// need explicit upper-bound rows.
+#[derive(Clone, Debug)]
+pub struct TrdModel {
+    order: usize,
+    objective: Vec<f64>,
+    constraints: Vec<Constraint>,
+}
+
+impl TrdModel {
+    /// Builds the model for `graph`.
+    ///
+    /// The graph is expected to have its vertices numbered from `0` to `n - 1`, as produced
+    /// by `utils::build_graph`.
+    #[must_use]
+    pub fn new(graph: &UndirectedGraph<usize>) -> Self {
+        let n = graph.order();
+        let mut objective = vec![1.0; 2 * n];
+        objective[n..].fill(2.0);
+
+        let mut constraints = Vec::with_capacity(3 * n);
+        for &v in graph.vertices() {
+            let neighbors: Vec<usize> = graph
+                .neighbors(&v)
+                .map(|n| n.copied().collect())
+                .unwrap_or_default();
+
+            constraints.push(Constraint {
+                coefficients: vec![(v, 1.0), (n + v, 1.0)],
+                sense: Sense::LessEqual,
+                rhs: 1.0,
+            });
+
+            let mut domination = vec![(v, 1.0), (n + v, 1.0)];
+            domination.extend(neighbors.iter().map(|&u| (n + u, 1.0)));
+            constraints.push(Constraint {
+                coefficients: domination,
+                sense: Sense::GreaterEqual,
+                rhs: 1.0,
+            });
+
+            let mut totality = vec![(v, -1.0), (n + v, -1.0)];
+            totality.extend(neighbors.iter().flat_map(|&u| [(u, 1.0), (n + u, 1.0)]));
+            constraints.push(Constraint {
+                coefficients: totality,
+                sense: Sense::GreaterEqual,
+                rhs: 0.0,
+            });
+        }
+
+        Self {
+            order: n,
+            objective,
+            constraints,
+        }
+    }
+
+    /// Returns the number of vertices of the modeled graph.
+    #[inline]
+    #[must_use]
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// Returns the objective coefficients, indexed by variable.
+    #[inline]
+    #[must_use]
+    pub fn objective(&self) -> &[f64] {
+        &self.objective
+    }
+
+    /// Returns the constraints of the model.
+    #[inline]
+    #[must_use]
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// Converts a 0/1 assignment of the model variables into a `Chromosome`.
+    ///
+    /// Values are rounded at `0.5`; `y_v` takes precedence over `x_v`.
+    #[must_use]
+    pub fn to_chromosome(&self, values: &[f64]) -> Chromosome {
+        let n = self.order;
+        let genes = (0..n)
+            .map(|v| {
+                if values[n + v] >= 0.5 {
+                    2
+                } else {
+                    u8::from(values[v] >= 0.5)
+                }
+            })
+            .collect();
+
+        Chromosome::new(genes)
+    }
+}
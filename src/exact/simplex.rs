@@ -0,0 +1,308 @@
+use super::model::{Constraint, Sense};
+
+const EPS: f64 = 1e-9;
+const FEASIBILITY_TOLERANCE: f64 = 1e-7;
+/// Degenerate pivots tolerated before switching to Bland's rule to avoid cycling.
+const MAX_DEGENERATE_PIVOTS: usize = 50;
+
+/// Result of solving a linear relaxation.
+#[derive(Clone, Debug)]
+pub(crate) enum LpOutcome {
+    /// An optimal vertex was found.
+    Optimal {
+        /// Objective value at the optimum.
+        value: f64,
+        /// Value of every variable, fixed ones included.
+        values: Vec<f64>,
+    },
+    /// No point satisfies the constraints.
+    Infeasible,
+    /// The objective decreases without bound.
+    Unbounded,
+}
+
+/// Dense simplex tableau. The last row holds the reduced costs and `-z`,
+/// the last column holds the right-hand side.
+struct Tableau {
+    rows: usize,
+    width: usize,
+    data: Vec<f64>,
+    basis: Vec<usize>,
+}
+
+impl Tableau {
+    fn at(&self, row: usize, col: usize) -> f64 {
+        self.data[row * self.width + col]
+    }
+
+    fn rhs(&self, row: usize) -> f64 {
+        self.at(row, self.width - 1)
+    }
+
+    fn pivot(&mut self, row: usize, col: usize) {
+        let width = self.width;
+        let pivot = self.at(row, col);
+        for value in &mut self.data[row * width..(row + 1) * width] {
+            *value /= pivot;
+        }
+
+        let pivot_row = self.data[row * width..(row + 1) * width].to_vec();
+        for i in (0..=self.rows).filter(|&i| i != row) {
+            let factor = self.at(i, col);
+            if factor.abs() > EPS {
+                for (value, &p) in self.data[i * width..(i + 1) * width]
+                    .iter_mut()
+                    .zip(&pivot_row)
+                {
+                    *value -= factor * p;
+                }
+            }
+        }
+
+        self.basis[row] = col;
+    }
+
+    /// Runs primal simplex iterations allowing only columns `< allowed` to enter.
+    ///
+    /// Returns `false` if the problem is unbounded.
+    fn optimize(&mut self, allowed: usize) -> bool {
+        let cost_row = self.rows;
+        let mut degenerate = 0;
+
+        loop {
+            let entering = if degenerate > MAX_DEGENERATE_PIVOTS {
+                (0..allowed).find(|&j| self.at(cost_row, j) < -EPS)
+            } else {
+                (0..allowed)
+                    .filter(|&j| self.at(cost_row, j) < -EPS)
+                    .min_by(|&a, &b| self.at(cost_row, a).total_cmp(&self.at(cost_row, b)))
+            };
+
+            let Some(col) = entering else {
+                return true;
+            };
+
+            let leaving = (0..self.rows)
+                .filter(|&i| self.at(i, col) > EPS)
+                .map(|i| (i, self.rhs(i) / self.at(i, col)))
+                .min_by(|&(i, a), &(k, b)| {
+                    a.total_cmp(&b)
+                        .then_with(|| self.basis[i].cmp(&self.basis[k]))
+                });
+
+            let Some((row, ratio)) = leaving else {
+                return false;
+            };
+
+            if ratio < EPS {
+                degenerate += 1;
+            }
+
+            self.pivot(row, col);
+        }
+    }
+}
+
+/// A constraint restricted to the free variables, normalized to `a·x <= b` or `a·x = b`.
+struct Row {
+    coefficients: Vec<(usize, f64)>,
+    equal: bool,
+    rhs: f64,
+}
+
+impl Row {
+    fn needs_artificial(&self) -> bool {
+        self.equal || self.rhs < 0.0
+    }
+}
+
+/// Substitutes the fixed variables into `constraints`.
+///
+/// Returns `None` if a constraint left without free variables is violated.
+fn normalize_rows(
+    constraints: &[Constraint],
+    fixed: &[Option<f64>],
+    column_of: &[usize],
+) -> Option<Vec<Row>> {
+    let mut rows = Vec::with_capacity(constraints.len());
+    for constraint in constraints {
+        let sign = if constraint.sense == Sense::GreaterEqual {
+            -1.0
+        } else {
+            1.0
+        };
+
+        let mut rhs = constraint.rhs;
+        let mut coefficients = Vec::with_capacity(constraint.coefficients.len());
+        for &(j, a) in &constraint.coefficients {
+            match fixed[j] {
+                Some(value) => rhs -= a * value,
+                None => coefficients.push((column_of[j], sign * a)),
+            }
+        }
+        rhs *= sign;
+
+        let equal = constraint.sense == Sense::Equal;
+        if coefficients.is_empty() {
+            let satisfied = if equal {
+                rhs.abs() <= FEASIBILITY_TOLERANCE
+            } else {
+                rhs >= -FEASIBILITY_TOLERANCE
+            };
+            if !satisfied {
+                return None;
+            }
+            continue;
+        }
+
+        rows.push(Row {
+            coefficients,
+            equal,
+            rhs,
+        });
+    }
+
+    Some(rows)
+}
+
+/// Builds the initial tableau with columns ordered as structural, slack, artificial.
+///
+/// Returns the tableau and the index of the first artificial column.
+fn initial_tableau(rows: &[Row], structural: usize) -> (Tableau, usize) {
+    let m = rows.len();
+    let slack_count = rows.iter().filter(|row| !row.equal).count();
+    let artificial_start = structural + slack_count;
+    let columns = artificial_start + rows.iter().filter(|row| row.needs_artificial()).count();
+    let width = columns + 1;
+
+    let mut tableau = Tableau {
+        rows: m,
+        width,
+        data: vec![0.0; (m + 1) * width],
+        basis: vec![0; m],
+    };
+
+    let mut next_slack = structural;
+    let mut next_artificial = artificial_start;
+    for (i, row) in rows.iter().enumerate() {
+        let sign = if row.rhs < 0.0 { -1.0 } else { 1.0 };
+        for &(j, a) in &row.coefficients {
+            tableau.data[i * width + j] += sign * a;
+        }
+        tableau.data[i * width + columns] = sign * row.rhs;
+
+        if !row.equal {
+            tableau.data[i * width + next_slack] = sign;
+            tableau.basis[i] = next_slack;
+            next_slack += 1;
+        }
+        if row.needs_artificial() {
+            tableau.data[i * width + next_artificial] = 1.0;
+            tableau.basis[i] = next_artificial;
+            next_artificial += 1;
+        }
+    }
+
+    (tableau, artificial_start)
+}
+
+/// Phase 1: minimizes the sum of the artificial variables and drives them out of the basis.
+///
+/// Returns `false` if the constraints are infeasible.
+fn phase_one(tableau: &mut Tableau, artificial_start: usize) -> bool {
+    let m = tableau.rows;
+    let width = tableau.width;
+    let columns = width - 1;
+    if artificial_start == columns {
+        return true;
+    }
+
+    for i in (0..m).filter(|&i| tableau.basis[i] >= artificial_start) {
+        for j in (0..artificial_start).chain([columns]) {
+            tableau.data[m * width + j] -= tableau.at(i, j);
+        }
+    }
+
+    tableau.optimize(columns);
+    if -tableau.rhs(m) > FEASIBILITY_TOLERANCE {
+        return false;
+    }
+
+    for i in 0..m {
+        if tableau.basis[i] >= artificial_start {
+            if let Some(col) = (0..artificial_start).find(|&j| tableau.at(i, j).abs() > EPS) {
+                tableau.pivot(i, col);
+            }
+        }
+    }
+
+    true
+}
+
+/// Solves `min c·x` subject to `constraints`, `x >= 0`, with some variables fixed.
+///
+/// Free variables have no upper bound other than the ones implied by the constraints.
+/// The relaxation is solved with a two-phase dense tableau simplex.
+pub(crate) fn solve_relaxation(
+    objective: &[f64],
+    constraints: &[Constraint],
+    fixed: &[Option<f64>],
+) -> LpOutcome {
+    let mut column_of = vec![usize::MAX; objective.len()];
+    let mut free = Vec::new();
+    for (j, value) in fixed.iter().enumerate() {
+        if value.is_none() {
+            column_of[j] = free.len();
+            free.push(j);
+        }
+    }
+
+    let Some(rows) = normalize_rows(constraints, fixed, &column_of) else {
+        return LpOutcome::Infeasible;
+    };
+
+    let (mut tableau, artificial_start) = initial_tableau(&rows, free.len());
+    if !phase_one(&mut tableau, artificial_start) {
+        return LpOutcome::Infeasible;
+    }
+
+    // Phase 2: the original objective, with artificial columns barred from entering.
+    let m = tableau.rows;
+    let width = tableau.width;
+    let cost = |j: usize| {
+        if j < free.len() {
+            objective[free[j]]
+        } else {
+            0.0
+        }
+    };
+    for j in 0..width {
+        let direct = if j < width - 1 { cost(j) } else { 0.0 };
+        let basic: f64 = (0..m)
+            .map(|i| cost(tableau.basis[i]) * tableau.at(i, j))
+            .sum();
+        tableau.data[m * width + j] = direct - basic;
+    }
+
+    if !tableau.optimize(artificial_start) {
+        return LpOutcome::Unbounded;
+    }
+
+    let mut values: Vec<f64> = fixed.iter().map(|value| value.unwrap_or(0.0)).collect();
+    for (i, &column) in tableau.basis.iter().enumerate() {
+        if column < free.len() {
+            values[free[column]] = tableau.rhs(i);
+        }
+    }
+
+    let constant: f64 = fixed
+        .iter()
+        .zip(objective)
+        .filter_map(|(value, c)| value.map(|v| v * c))
+        .sum();
+
+    LpOutcome::Optimal {
+        value: constant - tableau.rhs(m),
+        values,
+    }
+}
@@ -0,0 +1,309 @@
+use std::fmt;
+
+use kambo_graph::{graphs::simple::UndirectedGraph, Graph};
+
+use super::{
+    model::TrdModel,
+    simplex::{solve_relaxation, LpOutcome},
+};
+use crate::{csr::CsrGraph, genetic::Chromosome};
+
+/// Tolerance used to decide whether an LP value is integral.
+const INTEGRALITY_TOLERANCE: f64 = 1e-6;
+
+/// Errors reported by the exact solver.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExactError {
+    /// The graph admits no total Roman dominating function (it has an isolated vertex).
+    Infeasible,
+}
+
+impl fmt::Display for ExactError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Infeasible => write!(
+                f,
+                "the graph has no total Roman dominating function (isolated vertex)"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ExactError {}
+
+/// Best labeling found by [`ExactSolver`] together with its certificate.
+#[derive(Clone, Debug)]
+pub struct ExactSolution {
+    chromosome: Chromosome,
+    lower_bound: usize,
+    nodes: usize,
+}
+
+impl ExactSolution {
+    /// Returns the best total Roman dominating function found.
+    #[inline]
+    #[must_use]
+    pub fn chromosome(&self) -> &Chromosome {
+        &self.chromosome
+    }
+
+    /// Returns the weight of the best labeling found.
+    #[inline]
+    #[must_use]
+    pub fn weight(&self) -> usize {
+        self.chromosome.fitness()
+    }
+
+    /// Returns a proven lower bound on the total Roman domination number.
+    #[inline]
+    #[must_use]
+    pub fn lower_bound(&self) -> usize {
+        self.lower_bound
+    }
+
+    /// Returns the number of branch-and-bound nodes that were solved.
+    #[inline]
+    #[must_use]
+    pub fn nodes(&self) -> usize {
+        self.nodes
+    }
+
+    /// Returns `true` if the weight matches the lower bound, i.e. the labeling is optimal.
+    #[inline]
+    #[must_use]
+    pub fn is_optimal(&self) -> bool {
+        self.weight() == self.lower_bound
+    }
+}
+
+/// LP-based branch-and-bound solver for the total Roman domination ILP ([`TrdModel`]).
+///
+/// Each node solves the LP relaxation with a dense simplex, prunes on `⌈LP⌉`, rounds the
+/// relaxation into a candidate labeling repaired by [`Chromosome::fix`], and branches on the
+/// most fractional variable. Intended for small instances (up to roughly a hundred vertices).
+#[derive(Clone, Debug, Default)]
+pub struct ExactSolver {
+    node_limit: Option<usize>,
+}
+
+impl ExactSolver {
+    /// Creates a solver without a node limit.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stops the search after `limit` nodes.
+    ///
+    /// When the limit is hit, the returned solution is the best found so far and its
+    /// [`ExactSolution::lower_bound`] may be below its weight.
+    #[inline]
+    #[must_use]
+    pub fn with_node_limit(mut self, limit: usize) -> Self {
+        self.node_limit = Some(limit);
+        self
+    }
+
+    /// Computes a minimum total Roman dominating function of `graph`.
+    ///
+    /// # Errors
+    /// - [`ExactError::Infeasible`] if `graph` has an isolated vertex.
+    #[inline]
+    pub fn solve(&self, graph: &UndirectedGraph<usize>) -> Result<ExactSolution, ExactError> {
+        self.solve_from(graph, None)
+    }
+
+    /// Same as [`ExactSolver::solve`], starting from a known labeling (e.g. the GA result).
+    ///
+    /// A valid `incumbent` lets the search prune from the first node; an invalid one is ignored.
+    ///
+    /// # Errors
+    /// - [`ExactError::Infeasible`] if `graph` has an isolated vertex.
+    pub fn solve_from(
+        &self,
+        graph: &UndirectedGraph<usize>,
+        incumbent: Option<&Chromosome>,
+    ) -> Result<ExactSolution, ExactError> {
+        // Labeling every vertex with 1 is valid exactly when no vertex is isolated.
+        let mut best = Chromosome::new(vec![1; graph.order()]);
+        if !best.is_valid(graph) {
+            return Err(ExactError::Infeasible);
+        }
+        if let Some(incumbent) = incumbent {
+            if incumbent.fitness() < best.fitness() && incumbent.is_valid(graph) {
+                best = incumbent.clone();
+            }
+        }
+
+        let model = TrdModel::new(graph);
+        let csr = CsrGraph::new(graph);
+        let variables = model.objective().len();
+        let mut stack: Vec<(Vec<Option<f64>>, usize)> = vec![(vec![None; variables], 0)];
+        let mut nodes = 0;
+
+        while let Some((fixed, parent_bound)) = stack.pop() {
+            if parent_bound >= best.fitness() {
+                continue;
+            }
+            if self.node_limit.is_some_and(|limit| nodes >= limit) {
+                stack.push((fixed, parent_bound));
+                break;
+            }
+            nodes += 1;
+
+            let (value, values) =
+                match solve_relaxation(model.objective(), model.constraints(), &fixed) {
+                    LpOutcome::Optimal { value, values } => (value, values),
+                    LpOutcome::Infeasible => continue,
+                    LpOutcome::Unbounded => {
+                        unreachable!("the objective is non-negative, so the relaxation is bounded")
+                    }
+                };
+
+            let bound = lp_bound(value);
+            if bound >= best.fitness() {
+                continue;
+            }
+
+            let mut candidate = model.to_chromosome(&values);
+            candidate.fix(&csr);
+            if candidate.fitness() < best.fitness() && candidate.is_valid(graph) {
+                best = candidate;
+            }
+
+            if bound >= best.fitness() {
+                continue;
+            }
+
+            if let Some(j) = most_fractional(&values, &fixed) {
+                let mut down = fixed.clone();
+                down[j] = Some(0.0);
+                let mut up = fixed;
+                up[j] = Some(1.0);
+
+                stack.push((down, bound));
+                stack.push((up, bound));
+            }
+        }
+
+        let lower_bound = stack
+            .iter()
+            .map(|&(_, bound)| bound)
+            .filter(|&bound| bound < best.fitness())
+            .min()
+            .unwrap_or_else(|| best.fitness());
+
+        Ok(ExactSolution {
+            chromosome: best,
+            lower_bound,
+            nodes,
+        })
+    }
+}
+
+/// Rounds an LP value up to the smallest integer weight it allows.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn lp_bound(value: f64) -> usize {
+    (value - INTEGRALITY_TOLERANCE).ceil().max(0.0) as usize
+}
+
+/// Returns the free variable whose value is closest to `0.5`, if any is fractional.
+fn most_fractional(values: &[f64], fixed: &[Option<f64>]) -> Option<usize> {
+    values
+        .iter()
+        .zip(fixed)
+        .enumerate()
+        .filter(|(_, (_, fixed))| fixed.is_none())
+        .map(|(j, (&value, _))| (j, value - value.floor()))
+        .filter(|&(_, fraction)| {
+            fraction > INTEGRALITY_TOLERANCE && fraction < 1.0 - INTEGRALITY_TOLERANCE
+        })
+        .min_by(|&(_, a), &(_, b)| (a - 0.5).abs().total_cmp(&(b - 0.5).abs()))
+        .map(|(j, _)| j)
+}
+
+#[cfg(test)]
+mod tests {
+    use kambo_graph::GraphMut;
+
+    use super::*;
+
+    fn graph(
+        order: usize,
+        edges: impl IntoIterator<Item = (usize, usize)>,
+    ) -> UndirectedGraph<usize> {
+        let mut graph = UndirectedGraph::<usize>::new_undirected();
+        for v in 0..order {
+            graph.add_vertex(v).unwrap();
+        }
+        for (u, v) in edges {
+            graph.add_edge(&u, &v).unwrap();
+        }
+        graph
+    }
+
+    fn path(order: usize) -> UndirectedGraph<usize> {
+        graph(order, (1..order).map(|v| (v - 1, v)))
+    }
+
+    fn cycle(order: usize) -> UndirectedGraph<usize> {
+        graph(order, (0..order).map(|v| (v, (v + 1) % order)))
+    }
+
+    fn star(order: usize) -> UndirectedGraph<usize> {
+        graph(order, (1..order).map(|v| (0, v)))
+    }
+
+    fn complete(order: usize) -> UndirectedGraph<usize> {
+        graph(
+            order,
+            (0..order).flat_map(|u| (u + 1..order).map(move |v| (u, v))),
+        )
+    }
+
+    fn assert_optimum(graph: &UndirectedGraph<usize>, expected: usize) {
+        let solution = ExactSolver::new().solve(graph).unwrap();
+
+        assert!(solution.is_optimal());
+        assert_eq!(solution.weight(), expected);
+        assert_eq!(solution.lower_bound(), expected);
+        assert!(solution.chromosome().is_valid(graph));
+    }
+
+    #[test]
+    fn paths_and_cycles_need_one_per_vertex() {
+        for order in 2..=12 {
+            assert_optimum(&path(order), order);
+        }
+        for order in 3..=12 {
+            assert_optimum(&cycle(order), order);
+        }
+    }
+
+    #[test]
+    fn stars_need_three() {
+        assert_optimum(&star(2), 2);
+        for order in 3..=12 {
+            assert_optimum(&star(order), 3);
+        }
+    }
+
+    #[test]
+    fn complete_graphs_need_three() {
+        assert_optimum(&complete(2), 2);
+        for order in 3..=10 {
+            assert_optimum(&complete(order), 3);
+        }
+    }
+
+    #[test]
+    fn isolated_vertex_is_infeasible() {
+        let graph = graph(4, [(0, 1), (1, 2)]);
+
+        assert_eq!(
+            ExactSolver::new().solve(&graph).unwrap_err(),
+            ExactError::Infeasible
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use std::{collections::VecDeque, fmt};
+
+use kambo_graph::{graphs::simple::UndirectedGraph, Graph, GraphMut};
+
+/// Reasons why a graph admits no total Roman dominating function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FeasibilityError {
+    /// The graph has isolated vertices, which no neighbor can dominate.
+    IsolatedVertices(Vec<usize>),
+}
+
+impl fmt::Display for FeasibilityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IsolatedVertices(_) => write!(
+                f,
+                "the graph has no total Roman dominating function, it has isolated vertices"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FeasibilityError {}
+
+/// Structural analysis of a graph, run before solving it.
+///
+/// A total Roman dominating function exists if and only if the graph has no isolated vertex:
+/// every vertex needs a neighbor with a positive label.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Feasibility {
+    isolated: Vec<usize>,
+    components: Vec<Vec<usize>>,
+}
+
+impl Feasibility {
+    /// Analyzes `graph`, finding its isolated vertices and connected components.
+    ///
+    /// # Parameters
+    /// - `graph: &UndirectedGraph<usize>`: The graph, with vertices `0..n`.
+    ///
+    /// # Returns
+    /// - The analysis. Components are sorted by their smallest vertex, and each component is
+    ///   sorted; isolated vertices are also reported as single-vertex components.
+    #[must_use]
+    pub fn analyze(graph: &UndirectedGraph<usize>) -> Self {
+        let order = graph.order();
+        let mut seen = vec![false; order];
+        let mut components = Vec::new();
+
+        for start in 0..order {
+            if seen[start] {
+                continue;
+            }
+
+            seen[start] = true;
+            let mut component = vec![start];
+            let mut queue = VecDeque::from([start]);
+
+            while let Some(v) = queue.pop_front() {
+                for &n in graph.neighbors(&v).into_iter().flatten() {
+                    if !seen[n] {
+                        seen[n] = true;
+                        component.push(n);
+                        queue.push_back(n);
+                    }
+                }
+            }
+
+            component.sort_unstable();
+            components.push(component);
+        }
+
+        let isolated = (0..order)
+            .filter(|v| graph.degree(v).unwrap_or(0) == 0)
+            .collect();
+
+        Self {
+            isolated,
+            components,
+        }
+    }
+
+    /// Returns the isolated vertices, in increasing order.
+    #[inline]
+    #[must_use]
+    pub fn isolated_vertices(&self) -> &[usize] {
+        &self.isolated
+    }
+
+    /// Returns the connected components of the graph.
+    #[inline]
+    #[must_use]
+    pub fn components(&self) -> &[Vec<usize>] {
+        &self.components
+    }
+
+    /// Returns `true` if the graph admits a total Roman dominating function.
+    #[inline]
+    #[must_use]
+    pub fn is_feasible(&self) -> bool {
+        self.isolated.is_empty()
+    }
+
+    /// Checks that the graph admits a total Roman dominating function.
+    ///
+    /// # Errors
+    /// - [`FeasibilityError::IsolatedVertices`] with the isolated vertices, if there are any.
+    pub fn check(&self) -> Result<(), FeasibilityError> {
+        if self.is_feasible() {
+            Ok(())
+        } else {
+            Err(FeasibilityError::IsolatedVertices(self.isolated.clone()))
+        }
+    }
+}
+
+/// Builds the graph induced by the vertices that are not isolated.
+///
+/// Solving total Roman domination on this graph answers the problem for the rest of the
+/// original graph, with the isolated vertices reported apart.
+///
+/// # Parameters
+/// - `graph: &UndirectedGraph<usize>`: The graph, with vertices `0..n`.
+///
+/// # Returns
+/// - The reduced graph, with vertices `0..k`.
+/// - For each vertex of the reduced graph, the vertex of `graph` it comes from.
+#[must_use]
+pub fn remove_isolated(graph: &UndirectedGraph<usize>) -> (UndirectedGraph<usize>, Vec<usize>) {
+    let kept: Vec<usize> = (0..graph.order())
+        .filter(|v| graph.degree(v).unwrap_or(0) > 0)
+        .collect();
+
+    let mut index = vec![usize::MAX; graph.order()];
+    let mut reduced = UndirectedGraph::<usize>::new_undirected();
+    for (new, &old) in kept.iter().enumerate() {
+        index[old] = new;
+        reduced.add_vertex(new).ok();
+    }
+
+    for &old in &kept {
+        for &n in graph.neighbors(&old).into_iter().flatten() {
+            let (u, v) = (index[old], index[n]);
+            if u < v {
+                reduced.add_edge(&u, &v).ok();
+            }
+        }
+    }
+
+    (reduced, kept)
+}
@@ -0,0 +1,395 @@
+use std::{
+    fmt::Write as _,
+    fs,
+    io::{self, BufRead, Write},
+    path::Path,
+    time::Duration,
+};
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use super::{Chromosome, Population};
+
+/// Version of the checkpoint format written by [`Checkpoint::write`].
+const FORMAT: u32 = 2;
+
+/// Snapshot of a running trial, enough to continue it exactly where it stopped.
+///
+/// Restoring a checkpoint and running the remaining generations with the same operators gives
+/// the same result as the uninterrupted trial, since the generator state is saved with the
+/// population.
+///
+/// The text format has one `key value` line per field, with one `config` line per line of the
+/// settings, followed by the lines of [`Population::write`]:
+///
+/// ```text
+/// format 2
+/// graph p_hat1500-3.clq
+/// config trials = 30
+/// config seed = 42
+/// ...
+/// trial 1
+/// seed 42
+/// generation 120
+/// stagnant 15
+/// evaluations 6050
+/// elapsed 1834211
+/// rng <seed as 64 hex digits> <stream> <word position>
+/// best 0120...
+/// replacement generational 0
+/// chromosome 0210...
+/// ```
+#[derive(Clone)]
+pub struct Checkpoint {
+    /// Name of the graph the trial runs on.
+    pub graph: String,
+    /// Settings of the run, as text, so that a restore with other settings can be refused.
+    pub config: String,
+    /// Number of the trial, from `1`.
+    pub trial: usize,
+    /// Base seed of the run the trial belongs to.
+    pub seed: u64,
+    /// Generations run so far.
+    pub generation: usize,
+    /// Generations since the best fitness last improved.
+    pub stagnant_generations: usize,
+    /// Chromosomes evaluated so far, the initial population included.
+    pub evaluations: usize,
+    /// Wall-clock time spent in the trial so far.
+    pub elapsed: Duration,
+    /// Best chromosome found so far, which may no longer be in the population.
+    pub best: Chromosome,
+    /// Generator of the trial, in its current state.
+    pub rng: ChaCha8Rng,
+    /// The population after `generation` generations.
+    pub population: Population,
+}
+
+impl Checkpoint {
+    /// Writes the checkpoint in the format read by [`Checkpoint::read`].
+    ///
+    /// # Parameters
+    /// - `out: &mut W`: The destination.
+    ///
+    /// # Errors
+    /// - The errors of `out`.
+    pub fn write<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let seed = self
+            .rng
+            .get_seed()
+            .iter()
+            .fold(String::with_capacity(64), |mut hex, byte| {
+                let _ = write!(hex, "{byte:02x}");
+                hex
+            });
+
+        writeln!(out, "format {FORMAT}")?;
+        writeln!(out, "graph {}", self.graph)?;
+        for line in self.config.lines() {
+            if line.is_empty() {
+                writeln!(out, "config")?;
+            } else {
+                writeln!(out, "config {line}")?;
+            }
+        }
+        writeln!(out, "trial {}", self.trial)?;
+        writeln!(out, "seed {}", self.seed)?;
+        writeln!(out, "generation {}", self.generation)?;
+        writeln!(out, "stagnant {}", self.stagnant_generations)?;
+        writeln!(out, "evaluations {}", self.evaluations)?;
+        writeln!(out, "elapsed {}", self.elapsed.as_micros())?;
+        writeln!(
+            out,
+            "rng {seed} {} {}",
+            self.rng.get_stream(),
+            self.rng.get_word_pos()
+        )?;
+        writeln!(out, "best {}", self.best)?;
+        self.population.write(out)
+    }
+
+    /// Reads a checkpoint written by [`Checkpoint::write`]. Empty lines and lines starting
+    /// with `#` are skipped.
+    ///
+    /// # Parameters
+    /// - `input: R`: The source.
+    ///
+    /// # Errors
+    /// - An error of kind [`io::ErrorKind::InvalidData`] when a field is missing or malformed,
+    ///   the format version is unknown, or the population is invalid (see
+    ///   [`Population::read`]).
+    /// - Other I/O errors of `input`, unchanged.
+    pub fn read<R: BufRead>(input: R) -> io::Result<Self> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+
+        let mut fields = Fields::default();
+        let mut population = Vec::new();
+        for line in input.lines() {
+            let line = line?;
+            let trimmed = line.trim();
+            let (key, value) = trimmed.split_once(' ').unwrap_or((trimmed, ""));
+            let value = value.trim();
+            match key {
+                "format" => fields.format = Some(parse(key, value)?),
+                "graph" => fields.graph = Some(value.to_string()),
+                "config" => {
+                    let config = fields.config.get_or_insert_with(String::new);
+                    config.push_str(value);
+                    config.push('\n');
+                }
+                "trial" => fields.trial = Some(parse(key, value)?),
+                "seed" => fields.seed = Some(parse(key, value)?),
+                "generation" => fields.generation = Some(parse(key, value)?),
+                "stagnant" => fields.stagnant = Some(parse(key, value)?),
+                "evaluations" => fields.evaluations = Some(parse(key, value)?),
+                "elapsed" => fields.elapsed = Some(parse(key, value)?),
+                "rng" => fields.rng = Some(parse_rng(value)?),
+                "best" => {
+                    fields.best = Some(
+                        value
+                            .parse()
+                            .map_err(|e| invalid(format!("invalid best chromosome: {e}")))?,
+                    );
+                }
+                _ => {
+                    population.extend_from_slice(line.as_bytes());
+                    population.push(b'\n');
+                }
+            }
+        }
+
+        let missing = |key: &str| invalid(format!("the checkpoint has no '{key}' line"));
+        let format: u32 = fields.format.ok_or_else(|| missing("format"))?;
+        if format != FORMAT {
+            return Err(invalid(format!(
+                "unknown checkpoint format {format}, expected {FORMAT}"
+            )));
+        }
+
+        let population = Population::read(population.as_slice())?;
+        let best: Chromosome = fields.best.ok_or_else(|| missing("best"))?;
+        let order = population.chromosomes()[0].genes().len();
+        if best.genes().len() != order {
+            return Err(invalid(format!(
+                "best chromosome of length {}, expected {order}",
+                best.genes().len()
+            )));
+        }
+
+        Ok(Self {
+            graph: fields.graph.ok_or_else(|| missing("graph"))?,
+            config: fields.config.ok_or_else(|| missing("config"))?,
+            trial: fields.trial.ok_or_else(|| missing("trial"))?,
+            seed: fields.seed.ok_or_else(|| missing("seed"))?,
+            generation: fields.generation.ok_or_else(|| missing("generation"))?,
+            stagnant_generations: fields.stagnant.ok_or_else(|| missing("stagnant"))?,
+            evaluations: fields.evaluations.ok_or_else(|| missing("evaluations"))?,
+            elapsed: Duration::from_micros(fields.elapsed.ok_or_else(|| missing("elapsed"))?),
+            best,
+            rng: fields.rng.ok_or_else(|| missing("rng"))?,
+            population,
+        })
+    }
+
+    /// Writes the checkpoint to `path`, replacing it atomically: the data goes to a temporary
+    /// file next to `path`, is synced and is then renamed over it, so an interruption leaves
+    /// either the previous checkpoint or the new one.
+    ///
+    /// # Errors
+    /// - The I/O errors of creating, writing, syncing or renaming the file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+
+        let mut out = io::BufWriter::new(fs::File::create(&temp)?);
+        self.write(&mut out)?;
+        out.into_inner()?.sync_all()?;
+        fs::rename(&temp, path)
+    }
+
+    /// Reads the checkpoint saved at `path`.
+    ///
+    /// # Errors
+    /// - The errors of opening the file and of [`Checkpoint::read`].
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::read(io::BufReader::new(fs::File::open(path)?))
+    }
+}
+
+/// Fields of a checkpoint as they are read, before checking that none is missing.
+#[derive(Default)]
+struct Fields {
+    format: Option<u32>,
+    graph: Option<String>,
+    config: Option<String>,
+    trial: Option<usize>,
+    seed: Option<u64>,
+    generation: Option<usize>,
+    stagnant: Option<usize>,
+    evaluations: Option<usize>,
+    elapsed: Option<u64>,
+    rng: Option<ChaCha8Rng>,
+    best: Option<Chromosome>,
+}
+
+fn parse<T: std::str::FromStr>(key: &str, value: &str) -> io::Result<T> {
+    value.parse().map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid value '{value}' for '{key}'"),
+        )
+    })
+}
+
+/// Rebuilds a generator from its seed, stream and word position.
+fn parse_rng(value: &str) -> io::Result<ChaCha8Rng> {
+    let invalid = || {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid generator state '{value}', expected 'seed stream position'"),
+        )
+    };
+
+    let [hex, stream, position] = value.split_whitespace().collect::<Vec<_>>()[..] else {
+        return Err(invalid());
+    };
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err(invalid());
+    }
+    let mut seed = [0u8; 32];
+    for (byte, pair) in seed.iter_mut().zip(hex.as_bytes().chunks_exact(2)) {
+        let pair = std::str::from_utf8(pair).map_err(|_| invalid())?;
+        *byte = u8::from_str_radix(pair, 16).map_err(|_| invalid())?;
+    }
+
+    let mut rng = ChaCha8Rng::from_seed(seed);
+    rng.set_stream(stream.parse().map_err(|_| invalid())?);
+    rng.set_word_pos(position.parse().map_err(|_| invalid())?);
+    Ok(rng)
+}
+
+#[cfg(test)]
+mod tests {
+    use kambo_graph::{graphs::simple::UndirectedGraph, GraphMut};
+
+    use super::*;
+    use crate::{
+        csr::CsrGraph,
+        genetic::{h1, h4, KTournament, RandomRelabel, Replacement, SinglePoint},
+    };
+
+    fn cycle(order: usize) -> CsrGraph {
+        let mut graph = UndirectedGraph::<usize>::new_undirected();
+        for v in 0..order {
+            graph.add_vertex(v).unwrap();
+        }
+        for v in 0..order {
+            graph.add_edge(&v, &((v + 1) % order)).unwrap();
+        }
+        CsrGraph::new(&graph)
+    }
+
+    /// Evolves `checkpoint` until it reaches `generations`, updating the best chromosome.
+    fn evolve(checkpoint: &mut Checkpoint, generations: usize, csr: &CsrGraph) {
+        let (selection, crossover, mutation) = (
+            KTournament::new(3),
+            SinglePoint::new(0.9),
+            RandomRelabel::new(0.2),
+        );
+        while checkpoint.generation < generations {
+            let stats = checkpoint.population.envolve(
+                &selection,
+                &crossover,
+                &mutation,
+                csr,
+                &mut checkpoint.rng,
+            );
+            checkpoint.generation += 1;
+            checkpoint.evaluations += stats.evaluations;
+
+            let best = checkpoint.population.best_chromosome().unwrap();
+            if best.fitness() < checkpoint.best.fitness() {
+                checkpoint.best = best.clone();
+                checkpoint.stagnant_generations = 0;
+            } else {
+                checkpoint.stagnant_generations += 1;
+            }
+        }
+    }
+
+    fn start(csr: &CsrGraph) -> Checkpoint {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        rng.set_stream(3);
+        let population = Population::new(12, &[h1, h4], csr, &mut rng)
+            .with_replacement(Replacement::Generational { elitism: 1 });
+        Checkpoint {
+            graph: "cycle.txt".to_string(),
+            config: "trials = 2\nseed = 7\n\n[algorithm]\ngenerations = 40\n".to_string(),
+            trial: 3,
+            seed: 7,
+            generation: 0,
+            stagnant_generations: 0,
+            evaluations: population.size(),
+            elapsed: Duration::from_micros(1_234_567),
+            best: population.best_chromosome().unwrap().clone(),
+            rng,
+            population,
+        }
+    }
+
+    fn text(checkpoint: &Checkpoint) -> String {
+        let mut out = Vec::new();
+        checkpoint.write(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn write_and_read_round_trip() {
+        let csr = cycle(30);
+        let mut checkpoint = start(&csr);
+        evolve(&mut checkpoint, 5, &csr);
+
+        let read = Checkpoint::read(text(&checkpoint).as_bytes()).unwrap();
+
+        assert_eq!(read.graph, checkpoint.graph);
+        assert_eq!(read.config, checkpoint.config);
+        assert_eq!(read.trial, 3);
+        assert_eq!(read.seed, 7);
+        assert_eq!(read.generation, 5);
+        assert_eq!(read.stagnant_generations, checkpoint.stagnant_generations);
+        assert_eq!(read.evaluations, checkpoint.evaluations);
+        assert_eq!(read.elapsed, checkpoint.elapsed);
+        assert_eq!(read.best.genes(), checkpoint.best.genes());
+        assert_eq!(read.rng, checkpoint.rng);
+        assert_eq!(
+            read.population.replacement(),
+            checkpoint.population.replacement()
+        );
+        assert_eq!(text(&read), text(&checkpoint));
+    }
+
+    #[test]
+    fn restored_run_continues_identically() {
+        let csr = cycle(30);
+        let mut uninterrupted = start(&csr);
+        evolve(&mut uninterrupted, 15, &csr);
+        let mut restored = Checkpoint::read(text(&uninterrupted).as_bytes()).unwrap();
+
+        evolve(&mut uninterrupted, 40, &csr);
+        evolve(&mut restored, 40, &csr);
+
+        assert_eq!(text(&restored), text(&uninterrupted));
+    }
+
+    #[test]
+    fn unknown_format_is_rejected() {
+        let csr = cycle(10);
+        let old = text(&start(&csr)).replacen("format 2", "format 1", 1);
+
+        let err = Checkpoint::read(old.as_bytes()).err().unwrap();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -1,7 +1,9 @@
-use std::collections::HashMap;
+use std::{fmt, str::FromStr};
 
 use kambo_graph::{graphs::simple::UndirectedGraph, Graph};
 
+use crate::csr::CsrGraph;
+
 /// Structure representing a chromosome in the CL-Total-RDGA.
 ///
 /// Each chromosome stores a configuration of labels \{0, 1, 2\} for the vertices of a graph,
@@ -11,18 +13,157 @@ use kambo_graph::{graphs::simple::UndirectedGraph, Graph};
 /// - `genes: Vec<u8>`: A vector that stores the labels for each vertex in the graph.
 ///   - `0`: Must have a vertex labeled with the value `2` in its neighborhood.
 ///   - `1 | 2`: Must have a vertex labeled with `f > 0` in its neighborhood.
-/// - `neighbors_cache`: cache
+/// - `weight: usize`: The sum of the genes, kept up to date on every label change.
+/// - `counters`: Per-vertex counts of positive and `2` neighbors, built on the first repair.
+/// - `repaired: bool`: Whether [`Chromosome::fix`] has changed a label since creation.
 #[derive(Clone, Debug)]
 pub struct Chromosome {
     genes: Vec<u8>,
-    neighbors_cache: Option<NeighborsCache>,
+    weight: usize,
+    counters: Option<LabelCounters>,
+    repaired: bool,
+}
+
+/// A total Roman domination constraint broken by a labeling.
+///
+/// Produced by [`Chromosome::violations`], one entry per offending vertex.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Violation {
+    /// A vertex labeled `0` has no neighbor labeled `2`.
+    MissingTwoNeighbor(usize),
+    /// A vertex labeled `1` or `2` has no neighbor labeled `f > 0`.
+    MissingPositiveNeighbor(usize),
+    /// A vertex carries a label outside of \{0, 1, 2\}.
+    InvalidLabel {
+        /// The offending vertex.
+        vertex: usize,
+        /// The label found for it.
+        label: u8,
+    },
+    /// A vertex of the graph has no gene in the chromosome.
+    MissingLabel(usize),
+}
+
+impl Violation {
+    /// Returns the vertex that breaks the constraint.
+    #[inline]
+    #[must_use]
+    pub fn vertex(&self) -> usize {
+        match *self {
+            Self::MissingTwoNeighbor(vertex)
+            | Self::MissingPositiveNeighbor(vertex)
+            | Self::InvalidLabel { vertex, .. }
+            | Self::MissingLabel(vertex) => vertex,
+        }
+    }
+
+    /// Returns the same violation for the vertex `f(vertex)`.
+    ///
+    /// Useful to report violations with the vertex IDs of the input file instead of the
+    /// internal indices.
+    #[inline]
+    #[must_use]
+    pub fn map_vertex(self, f: impl FnOnce(usize) -> usize) -> Self {
+        match self {
+            Self::MissingTwoNeighbor(vertex) => Self::MissingTwoNeighbor(f(vertex)),
+            Self::MissingPositiveNeighbor(vertex) => Self::MissingPositiveNeighbor(f(vertex)),
+            Self::InvalidLabel { vertex, label } => Self::InvalidLabel {
+                vertex: f(vertex),
+                label,
+            },
+            Self::MissingLabel(vertex) => Self::MissingLabel(f(vertex)),
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingTwoNeighbor(v) => {
+                write!(f, "vertex {v} is labeled 0 but has no neighbor labeled 2")
+            }
+            Self::MissingPositiveNeighbor(v) => {
+                write!(
+                    f,
+                    "vertex {v} is labeled > 0 but has no neighbor labeled > 0"
+                )
+            }
+            Self::InvalidLabel { vertex, label } => {
+                write!(f, "vertex {vertex} has invalid label {label}")
+            }
+            Self::MissingLabel(v) => write!(f, "vertex {v} has no label"),
+        }
+    }
 }
 
+/// Error returned when parsing a [`Chromosome`] from a string of labels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseChromosomeError {
+    /// Index of the offending character, which is also the vertex it would label.
+    pub position: usize,
+    /// The character that is not `0`, `1` or `2`.
+    pub found: char,
+}
+
+impl fmt::Display for ParseChromosomeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid label '{}' at position {}, expected 0, 1 or 2",
+            self.found, self.position
+        )
+    }
+}
+
+impl std::error::Error for ParseChromosomeError {}
+
+/// Number of neighbors labeled `> 0` and labeled `2` of every vertex.
+///
+/// The adjacency itself lives in the shared [`CsrGraph`]; a label change only touches the
+/// counters of the neighbors of the changed vertex.
 #[derive(Clone, Debug)]
-struct NeighborsCache {
-    has_one_neighbor: Vec<bool>,
-    has_two_neighbor: Vec<bool>,
-    vertex_neighbors: HashMap<usize, Vec<usize>>,
+struct LabelCounters {
+    positive: Vec<u32>,
+    two: Vec<u32>,
+}
+
+impl LabelCounters {
+    fn new(genes: &[u8], graph: &CsrGraph) -> Self {
+        let mut counters = Self {
+            positive: vec![0; genes.len()],
+            two: vec![0; genes.len()],
+        };
+
+        for (vertex, &label) in genes.iter().enumerate() {
+            counters.shift(graph.neighbors(vertex), 0, label);
+        }
+
+        counters
+    }
+
+    /// Moves the counters of `neighbors` from a vertex labeled `old` to one labeled `new`.
+    #[inline]
+    fn shift(&mut self, neighbors: &[usize], old: u8, new: u8) {
+        let (old_positive, new_positive) = (u32::from(old > 0), u32::from(new > 0));
+        let (old_two, new_two) = (u32::from(old == 2), u32::from(new == 2));
+        if old_positive == new_positive && old_two == new_two {
+            return;
+        }
+
+        for &u in neighbors {
+            self.positive[u] = self.positive[u] + new_positive - old_positive;
+            self.two[u] = self.two[u] + new_two - old_two;
+        }
+    }
+
+    /// Tells whether `vertex`, labeled `label`, satisfies its total Roman domination condition.
+    #[inline]
+    fn satisfied(&self, vertex: usize, label: u8) -> bool {
+        match label {
+            0 => self.two[vertex] > 0,
+            _ => self.positive[vertex] > 0,
+        }
+    }
 }
 
 impl Chromosome {
@@ -36,9 +177,12 @@ impl Chromosome {
     #[inline]
     #[must_use]
     pub fn new(genes: Vec<u8>) -> Self {
+        let weight = genes.iter().map(|&x| usize::from(x)).sum();
         Self {
             genes,
-            neighbors_cache: None,
+            weight,
+            counters: None,
+            repaired: false,
         }
     }
 
@@ -47,12 +191,14 @@ impl Chromosome {
     /// The fitness is defined as the total weight of the total Roman domination function,
     /// which is the sum of all values in the gene vector.
     ///
+    /// The sum is maintained as labels change, so this is `O(1)`.
+    ///
     /// # Returns
-    /// - A `u32` value corresponding to the sum of the genes.
+    /// - A `usize` value corresponding to the sum of the genes.
     #[inline]
     #[must_use]
     pub fn fitness(&self) -> usize {
-        self.genes.iter().map(|&x| usize::from(x)).sum()
+        self.weight
     }
 
     /// Returns a slice containing the genes of the chromosome.
@@ -65,32 +211,111 @@ impl Chromosome {
         &self.genes
     }
 
-    fn initialize_cache(&mut self, graph: &UndirectedGraph<usize>) {
-        let vertex_count = self.genes.len();
-        let mut cache = NeighborsCache {
-            has_one_neighbor: vec![false; vertex_count],
-            has_two_neighbor: vec![false; vertex_count],
-            vertex_neighbors: HashMap::with_capacity(vertex_count),
-        };
+    /// Returns the sizes of the sets `V0`, `V1` and `V2` of vertices labeled `0`, `1` and `2`.
+    ///
+    /// # Returns
+    /// - `[|V0|, |V1|, |V2|]`. Genes with other values are not counted.
+    #[inline]
+    #[must_use]
+    pub fn label_counts(&self) -> [usize; 3] {
+        let mut counts = [0; 3];
+        for &gene in &self.genes {
+            if let Some(count) = counts.get_mut(usize::from(gene)) {
+                *count += 1;
+            }
+        }
+        counts
+    }
 
-        for vertex in graph.vertices() {
-            let neighbors: Vec<usize> = graph
-                .neighbors(vertex)
-                .map(|n| n.copied().collect())
-                .unwrap_or_default();
+    /// Sets the label of a single vertex.
+    ///
+    /// The fitness is updated in `O(1)`, and the neighbor counters, if already built by
+    /// [`Chromosome::fix`], in `O(deg(vertex))`, so a later call to `fix` sees the new label.
+    ///
+    /// # Parameters
+    /// - `vertex: usize`: The vertex whose label changes.
+    /// - `label: u8`: The new label (`0`, `1` or `2`).
+    /// - `graph: &CsrGraph`: The graph the chromosome labels.
+    ///
+    /// # Panics
+    /// - If `vertex` is out of bounds of the gene vector.
+    pub fn set_gene(&mut self, vertex: usize, label: u8, graph: &CsrGraph) {
+        let old = std::mem::replace(&mut self.genes[vertex], label);
+        self.weight = self.weight - usize::from(old) + usize::from(label);
 
-            cache.vertex_neighbors.insert(*vertex, neighbors);
+        if let Some(counters) = self.counters.as_mut() {
+            counters.shift(graph.neighbors(vertex), old, label);
         }
+    }
 
-        self.update_cache(&mut cache);
-        self.neighbors_cache = Some(cache);
+    /// Checks whether the chromosome is a total Roman dominating function of `graph`.
+    ///
+    /// Unlike [`Chromosome::fix`], this never modifies the genes; it is an independent
+    /// check that can be used to certify the solutions reported by the algorithm.
+    ///
+    /// # Parameters
+    /// - `graph: &UndirectedGraph<usize>`: The graph the labeling is checked against.
+    ///
+    /// # Returns
+    /// - `true` if [`Chromosome::violations`] finds no broken constraint.
+    #[inline]
+    #[must_use]
+    pub fn is_valid(&self, graph: &UndirectedGraph<usize>) -> bool {
+        self.violations(graph).is_empty()
     }
 
-    fn update_cache(&self, cache: &mut NeighborsCache) {
-        for (v, neighbors) in &cache.vertex_neighbors {
-            cache.has_one_neighbor[*v] = neighbors.iter().any(|&n| self.genes[n] > 0);
+    /// Lists every vertex of `graph` whose label breaks total Roman domination.
+    ///
+    /// The following conditions are checked for each vertex `v`:
+    /// - `f(v) = 0` requires a neighbor `u` with `f(u) = 2`.
+    /// - `f(v) > 0` requires a neighbor `u` with `f(u) > 0`.
+    /// - `f(v)` must exist and be one of `0`, `1` or `2`.
+    ///
+    /// # Parameters
+    /// - `graph: &UndirectedGraph<usize>`: The graph the labeling is checked against.
+    ///
+    /// # Returns
+    /// - A vector of [`Violation`], sorted by vertex. Empty if the labeling is valid.
+    #[must_use]
+    pub fn violations(&self, graph: &UndirectedGraph<usize>) -> Vec<Violation> {
+        let mut violations = Vec::new();
+
+        for &vertex in graph.vertices() {
+            let Some(&label) = self.genes.get(vertex) else {
+                violations.push(Violation::MissingLabel(vertex));
+                continue;
+            };
+
+            let mut neighbor_labels = graph
+                .neighbors(&vertex)
+                .into_iter()
+                .flatten()
+                .map(|&n| self.genes.get(n).copied().unwrap_or(0));
+
+            match label {
+                0 => {
+                    if !neighbor_labels.any(|l| l == 2) {
+                        violations.push(Violation::MissingTwoNeighbor(vertex));
+                    }
+                }
+                1 | 2 => {
+                    if !neighbor_labels.any(|l| l > 0) {
+                        violations.push(Violation::MissingPositiveNeighbor(vertex));
+                    }
+                }
+                _ => violations.push(Violation::InvalidLabel { vertex, label }),
+            }
+        }
+
+        violations.sort_unstable_by_key(Violation::vertex);
+        violations
+    }
 
-            cache.has_two_neighbor[*v] = neighbors.iter().any(|&n| self.genes[n] == 2);
+    /// Builds the neighbor counters if they are missing and returns them.
+    fn counters(&mut self, graph: &CsrGraph) -> LabelCounters {
+        match self.counters.take() {
+            Some(counters) => counters,
+            None => LabelCounters::new(&self.genes, graph),
         }
     }
 
@@ -103,90 +328,396 @@ impl Chromosome {
     /// The method iteratively adjusts the labels of vertices in the chromosome until these conditions are met.
     ///
     /// # Parameters
-    /// - `graph: &UndirectedGraph<u32>`: The graph representing the structure and relationships
+    /// - `graph: &CsrGraph`: The graph representing the structure and relationships
     ///   between vertices. The graph is used to determine the neighbors of each vertex.
     ///
     /// # Details
-    /// - If the `neighbors_cache` is not initialized, this method initializes it before proceeding.
-    /// - The method uses a cache to track which vertices have neighbors with specific labels (`1` or `2`)
-    ///   to optimize label correction.
-    /// - It modifies the gene vector in-place, ensuring all conditions of total Roman domination
-    ///   are satisfied.
+    /// - The neighbor counters are built on the first call, in `O(n + m)`; afterwards every
+    ///   label change costs `O(deg)`.
+    /// - Vertices are swept in index order until a sweep changes nothing:
+    ///   - a `0` without a `2` neighbor gets its first `0` neighbor raised to `2`, or, when all
+    ///     of its neighbors are labeled `1`, its first neighbor raised from `1` to `2`;
+    ///   - a `1` or `2` without a positive neighbor gets its first `0` neighbor raised to `1`.
     ///
-    /// # Panics
-    /// - The method will `panic` if a vertex has an invalid label. Valid labels are:
-    ///   - `0`: Requires at least one neighbor labeled `2`.
-    ///   - `1`: Requires at least one neighbor labeled with a value greater than `0`.
-    ///   - `2`: Requires at least one neighbor labeled with a value greater than `0`.
+    ///   A raised neighbor is visited again in the next sweep, since its own condition may now
+    ///   be broken.
+    /// - If any label is raised, [`Chromosome::was_repaired`] returns `true` from then on.
+    /// - On a graph without isolated vertices the result is always a valid total Roman
+    ///   dominating function. Isolated vertices cannot be repaired and are left as they are
+    ///   (see [`Chromosome::violations`]).
     ///
-    ///   The panic message includes the vertex index and the invalid label value, providing context for debugging:
-    ///   ```text
-    ///   Vertex with invalid label found! Index: {vertex_idx}, Value: {invalid}.
-    ///   Valid labels are: 0, 1, or 2.
-    pub fn fix(&mut self, graph: &UndirectedGraph<usize>) {
-        if self.neighbors_cache.is_none() {
-            self.initialize_cache(graph);
-        }
-
-        let mut cache = self.neighbors_cache.take().unwrap();
+    /// # Panics
+    /// - If a vertex has an invalid label (other than `0`, `1` or `2`).
+    pub fn fix(&mut self, graph: &CsrGraph) {
+        let mut counters = self.counters(graph);
         let mut modified = true;
         let mut visited = vec![false; self.genes.len()];
 
+        // Conditions are read as they stood when the sweep started, so a raise only counts
+        // from the next sweep on. Counters only grow here, so the old value of a counter is the
+        // current one minus what the sweep added, and `touched` lists the entries to clear.
+        let mut added = LabelCounters {
+            positive: vec![0; self.genes.len()],
+            two: vec![0; self.genes.len()],
+        };
+        let mut touched: Vec<usize> = Vec::new();
+
         while modified {
             modified = false;
 
-            for vertex in graph.vertices() {
-                let vertex_idx = { *vertex };
-                if visited[vertex_idx] {
+            for vertex in 0..self.genes.len() {
+                if visited[vertex] {
                     continue;
                 }
 
-                visited[vertex_idx] = true;
-                let neighbors = cache.vertex_neighbors.get(vertex).unwrap();
-                match self.genes.get(vertex_idx) {
-                    Some(&0) => {
-                        if !cache.has_two_neighbor[vertex_idx] {
-                            if let Some(&neighbor_idx) =
-                                neighbors.iter().find(|&&n| self.genes[n] == 0)
-                            {
-                                self.genes[neighbor_idx] = 2;
-                                visited[neighbor_idx] = false;
-                                modified = true;
-                            }
-                        }
-                    }
-                    Some(&1 | &2) => {
-                        if !cache.has_one_neighbor[vertex_idx] {
-                            // Encontra o primeiro vizinho com valor 0 para atualizar para 1
-                            if let Some(&neighbor_idx) =
-                                neighbors.iter().find(|&&n| self.genes[n] == 0)
-                            {
-                                self.genes[neighbor_idx] = 1;
-                                visited[neighbor_idx] = false;
-                                modified = true;
-                            }
-                        }
-                    }
-
-                    Some(&invalid) => {
-                        panic!(
+                visited[vertex] = true;
+                let (satisfied, new) = match self.genes[vertex] {
+                    0 => (counters.two[vertex] > added.two[vertex], 2),
+                    1 | 2 => (counters.positive[vertex] > added.positive[vertex], 1),
+                    invalid => panic!(
                         "Vértice com rótulo inválido encontrado! Índice: {vertex}, Valor: {invalid}. \
-                            Os rótulos válidos são: 0, 1, ou 2."
-                    );
-                    }
-                    None => {
-                        panic!(
-                            "Tentativa de acessar índice fora dos limites! Índice: {vertex}. \
-                        Verifique se o vetor de genes está consistente com o grafo.",
-                        );
-                    }
+                         Os rótulos válidos são: 0, 1, ou 2."
+                    ),
+                };
+                if satisfied {
+                    continue;
+                }
+
+                let neighbors = graph.neighbors(vertex);
+                let raise = match neighbors.iter().find(|&&n| self.genes[n] == 0) {
+                    Some(&neighbor) => Some((neighbor, 0)),
+                    // Um 0 cercado de 1 não tem vizinho 0 para subir: um vizinho 1 passa a 2,
+                    // a menos que algum vizinho já tenha subido a 2 nesta varredura.
+                    None if new == 2 && counters.two[vertex] == 0 => neighbors
+                        .iter()
+                        .find(|&&n| self.genes[n] == 1)
+                        .map(|&neighbor| (neighbor, 1)),
+                    None => None,
+                };
+                if let Some((neighbor, old)) = raise {
+                    self.repaired = true;
+                    self.genes[neighbor] = new;
+                    self.weight += usize::from(new - old);
+                    counters.shift(graph.neighbors(neighbor), old, new);
+                    added.shift(graph.neighbors(neighbor), old, new);
+                    touched.extend_from_slice(graph.neighbors(neighbor));
+                    visited[neighbor] = false;
+                    modified = true;
                 }
             }
 
-            if modified {
-                self.update_cache(&mut cache);
+            for &u in &touched {
+                added.positive[u] = 0;
+                added.two[u] = 0;
             }
+            touched.clear();
         }
-        self.neighbors_cache = Some(cache);
+
+        self.counters = Some(counters);
+    }
+
+    /// Returns `true` if [`Chromosome::fix`] has changed any label since the chromosome was
+    /// created, that is, if the labeling it was created with or later given was not valid.
+    #[inline]
+    #[must_use]
+    pub fn was_repaired(&self) -> bool {
+        self.repaired
+    }
+
+    /// Removes redundant weight from the chromosome with a first-improvement local search.
+    ///
+    /// Every vertex is visited in turn and its label is lowered (`2 → 0`, `2 → 1`, `1 → 0`)
+    /// whenever the vertex and all of its neighbors still satisfy the conditions of total
+    /// Roman domination afterwards. Passes are repeated until no label can be lowered.
+    ///
+    /// Since only labels are lowered, the fitness never increases; moves that would leave a
+    /// violated constraint behind are never taken, so a valid chromosome stays valid.
+    ///
+    /// # Parameters
+    /// - `graph: &CsrGraph`: The graph representing the structure and
+    ///   relationships between vertices.
+    pub fn remove_redundancy(&mut self, graph: &CsrGraph) {
+        let mut counters = self.counters(graph);
+
+        let mut improved = true;
+        while improved {
+            improved = false;
+
+            for vertex in 0..self.genes.len() {
+                let current = self.genes[vertex];
+                if current == 0 || current > 2 {
+                    continue;
+                }
+
+                let neighbors = graph.neighbors(vertex);
+                let Some(target) = (0..current).find(|&target| {
+                    let loses_positive = u32::from(target == 0);
+                    let loses_two = u32::from(current == 2);
+
+                    counters.satisfied(vertex, target)
+                        && neighbors.iter().all(|&u| match self.genes[u] {
+                            0 => counters.two[u] > loses_two,
+                            _ => counters.positive[u] > loses_positive,
+                        })
+                }) else {
+                    continue;
+                };
+
+                counters.shift(neighbors, current, target);
+                self.genes[vertex] = target;
+                self.weight -= usize::from(current - target);
+                improved = true;
+            }
+        }
+
+        self.counters = Some(counters);
+    }
+}
+
+impl fmt::Display for Chromosome {
+    /// Writes the labels as a string of digits, the label of vertex `v` at position `v`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &gene in &self.genes {
+            write!(f, "{gene}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Chromosome {
+    type Err = ParseChromosomeError;
+
+    /// Reads the format written by [`fmt::Display`]. The weight is recomputed and the repair
+    /// counters are rebuilt on demand, so the result behaves like the chromosome that was
+    /// written, except that [`Chromosome::was_repaired`] starts over as `false`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let genes = s
+            .chars()
+            .enumerate()
+            .map(|(position, found)| match found {
+                '0' => Ok(0),
+                '1' => Ok(1),
+                '2' => Ok(2),
+                _ => Err(ParseChromosomeError { position, found }),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self::new(genes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use kambo_graph::GraphMut;
+    use rand::prelude::*;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::csr::test_graphs::random_connected;
+
+    /// Cycle on `order` vertices, or a path if `closed` is false.
+    fn ring(order: usize, closed: bool) -> UndirectedGraph<usize> {
+        let mut graph = UndirectedGraph::<usize>::new_undirected();
+        for v in 0..order {
+            graph.add_vertex(v).unwrap();
+        }
+        for v in 1..order {
+            graph.add_edge(&(v - 1), &v).unwrap();
+        }
+        if closed {
+            graph.add_edge(&(order - 1), &0).unwrap();
+        }
+        graph
+    }
+
+    fn labeling(genes: &str) -> Chromosome {
+        genes.parse().unwrap()
+    }
+
+    fn assert_counters_match(chromosome: &Chromosome, graph: &CsrGraph) {
+        let counters = chromosome.counters.as_ref().unwrap();
+        let expected = LabelCounters::new(chromosome.genes(), graph);
+        assert_eq!(counters.positive, expected.positive);
+        assert_eq!(counters.two, expected.two);
+        assert_eq!(
+            chromosome.fitness(),
+            chromosome.genes().iter().map(|&g| usize::from(g)).sum()
+        );
+    }
+
+    #[test]
+    fn incremental_counters_match_a_recount() {
+        let mut rng = ChaCha8Rng::seed_from_u64(5);
+
+        for _ in 0..20 {
+            let graph = CsrGraph::new(&random_connected(30, 0.15, &mut rng));
+            let genes = (0..30).map(|_| rng.gen_range(0..=2)).collect();
+            let mut chromosome = Chromosome::new(genes);
+            chromosome.fix(&graph);
+            assert_counters_match(&chromosome, &graph);
+
+            for step in 0..200 {
+                let vertex = rng.gen_range(0..30);
+                chromosome.set_gene(vertex, rng.gen_range(0..=2), &graph);
+                match step % 50 {
+                    24 => chromosome.fix(&graph),
+                    49 => chromosome.remove_redundancy(&graph),
+                    _ => {}
+                }
+                assert_counters_match(&chromosome, &graph);
+            }
+        }
+    }
+
+    /// Tells whether no single label of a valid `chromosome` can be lowered without
+    /// breaking total Roman domination.
+    fn is_locally_minimal(chromosome: &Chromosome, graph: &UndirectedGraph<usize>) -> bool {
+        (0..chromosome.genes().len()).all(|vertex| {
+            (0..chromosome.genes()[vertex]).all(|target| {
+                let mut genes = chromosome.genes().to_vec();
+                genes[vertex] = target;
+                !Chromosome::new(genes).is_valid(graph)
+            })
+        })
+    }
+
+    #[test]
+    fn remove_redundancy_lowers_redundant_labels() {
+        let path = ring(4, false);
+        let mut chromosome = labeling("2222");
+        chromosome.remove_redundancy(&CsrGraph::new(&path));
+        assert_eq!(chromosome.genes(), [0, 2, 1, 1]);
+        assert_eq!(chromosome.fitness(), 4);
+
+        let cycle = ring(5, true);
+        let mut chromosome = labeling("22222");
+        chromosome.remove_redundancy(&CsrGraph::new(&cycle));
+        assert!(chromosome.is_valid(&cycle));
+        assert!(is_locally_minimal(&chromosome, &cycle));
+        assert!(chromosome.fitness() < 10);
+    }
+
+    #[test]
+    fn remove_redundancy_keeps_minimal_labelings() {
+        for (graph, genes) in [
+            (ring(4, false), "0220"),
+            (ring(4, true), "1111"),
+            (ring(5, true), "11111"),
+        ] {
+            let mut chromosome = labeling(genes);
+            chromosome.remove_redundancy(&CsrGraph::new(&graph));
+            assert_eq!(chromosome.to_string(), genes);
+        }
+    }
+
+    #[test]
+    fn remove_redundancy_keeps_repaired_labelings_valid() {
+        let mut rng = ChaCha8Rng::seed_from_u64(9);
+
+        for _ in 0..50 {
+            let graph = random_connected(25, 0.2, &mut rng);
+            let csr = CsrGraph::new(&graph);
+            let genes = (0..25).map(|_| rng.gen_range(0..=2)).collect();
+            let mut chromosome = Chromosome::new(genes);
+            chromosome.fix(&csr);
+            let before = chromosome.fitness();
+
+            chromosome.remove_redundancy(&csr);
+            assert!(chromosome.is_valid(&graph), "{chromosome}");
+            assert!(chromosome.fitness() <= before);
+            assert!(is_locally_minimal(&chromosome, &graph), "{chromosome}");
+        }
+    }
+
+    #[test]
+    fn fix_raises_a_one_around_a_zero_without_zero_neighbors() {
+        let path = ring(5, false);
+        let mut chromosome = labeling("11011");
+        chromosome.fix(&CsrGraph::new(&path));
+        assert_eq!(chromosome.genes(), [1, 2, 0, 1, 1]);
+        assert_eq!(chromosome.fitness(), 5);
+        assert!(chromosome.was_repaired());
+        assert!(chromosome.is_valid(&path));
+    }
+
+    #[test]
+    fn fix_always_returns_a_valid_labeling() {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+
+        for _ in 0..100 {
+            let graph = random_connected(20, 0.15, &mut rng);
+            let csr = CsrGraph::new(&graph);
+            let genes = (0..20).map(|_| rng.gen_range(0..=2)).collect();
+            let mut chromosome = Chromosome::new(genes);
+            chromosome.fix(&csr);
+            assert!(chromosome.is_valid(&graph), "{chromosome}");
+        }
+    }
+
+    #[test]
+    fn valid_labelings_have_no_violation() {
+        for (graph, genes) in [
+            (ring(4, false), "0220"),
+            (ring(5, false), "02220"),
+            (ring(5, true), "11111"),
+            (ring(6, true), "022022"),
+        ] {
+            let chromosome = labeling(genes);
+            assert!(chromosome.is_valid(&graph), "{genes}");
+            assert_eq!(chromosome.violations(&graph), [], "{genes}");
+        }
+    }
+
+    #[test]
+    fn zero_without_a_two_neighbor_is_reported() {
+        let path = ring(4, false);
+        let chromosome = labeling("0120");
+        assert!(!chromosome.is_valid(&path));
+        assert_eq!(
+            chromosome.violations(&path),
+            [Violation::MissingTwoNeighbor(0)]
+        );
+
+        let cycle = ring(5, true);
+        assert_eq!(
+            labeling("02210").violations(&cycle),
+            [Violation::MissingTwoNeighbor(4)]
+        );
+    }
+
+    #[test]
+    fn positive_without_a_positive_neighbor_is_reported() {
+        let path = ring(4, false);
+        let chromosome = labeling("2020");
+        assert!(!chromosome.is_valid(&path));
+        assert_eq!(
+            chromosome.violations(&path),
+            [
+                Violation::MissingPositiveNeighbor(0),
+                Violation::MissingPositiveNeighbor(2),
+            ]
+        );
+
+        let cycle = ring(5, true);
+        assert_eq!(
+            labeling("10210").violations(&cycle),
+            [
+                Violation::MissingPositiveNeighbor(0),
+                Violation::MissingTwoNeighbor(4),
+            ]
+        );
+    }
+
+    #[test]
+    fn labels_outside_the_range_are_reported() {
+        let path = ring(3, false);
+        let chromosome = Chromosome::new(vec![1, 3, 1]);
+        assert!(!chromosome.is_valid(&path));
+        assert_eq!(
+            chromosome.violations(&path),
+            [Violation::InvalidLabel {
+                vertex: 1,
+                label: 3
+            }]
+        );
     }
 }
@@ -1,59 +1,76 @@
-use kambo_graph::graphs::simple::UndirectedGraph;
 use rand::prelude::*;
 
 use super::chromosome::Chromosome;
+use crate::csr::CsrGraph;
 
 /// Trait defining crossover operations
 pub trait Crossover {
-    /// Performs crossover between two parent chromosomes
+    /// Performs crossover between two parent chromosomes, drawing every random choice from `rng`
     fn crossover(
         &self,
         parent1: &Chromosome,
         parent2: &Chromosome,
-        graph: &UndirectedGraph<usize>,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
     ) -> (Chromosome, Chromosome);
 }
 
-/// Single-point crossover implementation optimized for performance#[derive(Clone)]
+fn check_rate(crossover_rate: f64) {
+    assert!(
+        (0.0..=1.0).contains(&crossover_rate),
+        "Crossover probability must be between 0 and 1"
+    );
+}
+
+/// Implements `new` and `with_local_search` for an operator with the `crossover_rate` and
+/// `local_search` fields.
+macro_rules! rate_constructors {
+    ($name:ident) => {
+        impl $name {
+            /// Creates a new instance with a specified crossover rate.
+            ///
+            /// The crossover rate is the probability of applying the crossover to a pair of
+            /// parents: `0.0` never crosses them, and `1.0` always does.
+            ///
+            /// # Parameters
+            /// - `crossover_rate: f64`: Probability of crossing a pair of parents, in `[0.0, 1.0]`.
+            ///
+            /// # Panics
+            /// - If `crossover_rate` is outside the range `[0.0, 1.0]`, with the message
+            ///   `Crossover probability must be between 0 and 1`.
+            #[inline]
+            #[must_use]
+            pub fn new(crossover_rate: f64) -> Self {
+                check_rate(crossover_rate);
+                Self {
+                    crossover_rate,
+                    local_search: false,
+                }
+            }
+
+            /// Enables or disables the redundancy-removal local search on the offspring.
+            ///
+            /// When enabled, every child is passed through [`Chromosome::remove_redundancy`]
+            /// after being repaired with [`Chromosome::fix`], giving a memetic variant of the
+            /// algorithm.
+            #[inline]
+            #[must_use]
+            pub fn with_local_search(mut self, local_search: bool) -> Self {
+                self.local_search = local_search;
+                self
+            }
+        }
+    };
+}
+
+/// Single-point crossover: the children exchange the genes after a random cut point.
+#[derive(Clone, Debug)]
 pub struct SinglePoint {
     crossover_rate: f64,
+    local_search: bool,
 }
 
-impl SinglePoint {
-    /// Creates a new instance with a specified crossover rate.
-    ///
-    /// The crossover rate determines the probability of applying the crossover
-    /// operation to a pair of parents during the evolutionary process. The value
-    /// must be within the range `[0.0, 1.0]`, where:
-    /// - `0.0` means crossover is never applied.
-    /// - `1.0` means crossover is always applied.
-    ///
-    /// # Parameters
-    /// - `crossover_rate: f64`  
-    ///   A floating-point value representing the probability of crossover.  
-    ///   The value must satisfy `0.0 <= crossover_rate <= 1.0`.
-    ///
-    /// # Returns
-    /// A new instance of `Self` with the specified crossover rate.
-    ///
-    /// # Panics
-    /// This method will panic if:
-    /// - `crossover_rate` is outside the range `[0.0, 1.0]`.  
-    ///   The panic message will be:
-    ///   ```text
-    ///   Crossover probability must be between 0 and 1
-    ///   ```
-    ///
-    #[inline]
-    #[must_use]
-    pub fn new(crossover_rate: f64) -> Self {
-        assert!(
-            (0.0..=1.0).contains(&crossover_rate),
-            "Crossover probability must be between 0 and 1"
-        );
-        Self { crossover_rate }
-    }
-}
+rate_constructors!(SinglePoint);
 
 impl Crossover for SinglePoint {
     #[inline]
@@ -61,53 +78,359 @@ impl Crossover for SinglePoint {
         &self,
         parent1: &Chromosome,
         parent2: &Chromosome,
-        graph: &UndirectedGraph<usize>,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
     ) -> (Chromosome, Chromosome) {
-        let mut rng = thread_rng();
+        let genes1 = parent1.genes();
+        let genes2 = parent2.genes();
+        let len = genes1.len();
 
-        // Se não ocorrer crossover, retorna cópias dos pais
-        if !rng.gen_bool(self.crossover_rate) {
+        // Se não ocorrer crossover, ou se não houver ponto de corte (menos de 2 genes),
+        // retorna cópias dos pais
+        if len < 2 || !rng.gen_bool(self.crossover_rate) {
             return (
-                Chromosome::new(parent1.genes().to_vec()),
-                Chromosome::new(parent2.genes().to_vec()),
+                Chromosome::new(genes1.to_vec()),
+                Chromosome::new(genes2.to_vec()),
             );
         }
 
-        let genes1 = parent1.genes();
-        let genes2 = parent2.genes();
-        let len = genes1.len();
         let point = rng.gen_range(1..len);
 
+        // Cada gene é copiado uma única vez, direto para a posição final.
         let mut child1_genes = Vec::with_capacity(len);
+        child1_genes.extend_from_slice(&genes1[..point]);
+        child1_genes.extend_from_slice(&genes2[point..]);
+
         let mut child2_genes = Vec::with_capacity(len);
+        child2_genes.extend_from_slice(&genes2[..point]);
+        child2_genes.extend_from_slice(&genes1[point..]);
 
-        unsafe {
-            child1_genes.set_len(len);
-            child2_genes.set_len(len);
+        offspring(child1_genes, child2_genes, graph, self.local_search)
+    }
+}
 
-            // First child
-            std::ptr::copy_nonoverlapping(genes1.as_ptr(), child1_genes.as_mut_ptr(), point);
-            std::ptr::copy_nonoverlapping(
-                genes2.as_ptr().add(point),
-                child1_genes.as_mut_ptr().add(point),
-                len - point,
-            );
+/// Builds the two children from their genes, repairing them and, if `local_search` is set,
+/// removing their redundant labels.
+fn offspring(
+    child1_genes: Vec<u8>,
+    child2_genes: Vec<u8>,
+    graph: &CsrGraph,
+    local_search: bool,
+) -> (Chromosome, Chromosome) {
+    let mut child1 = Chromosome::new(child1_genes);
+    let mut child2 = Chromosome::new(child2_genes);
 
-            // Second child
-            std::ptr::copy_nonoverlapping(genes2.as_ptr(), child2_genes.as_mut_ptr(), point);
-            std::ptr::copy_nonoverlapping(
-                genes1.as_ptr().add(point),
-                child2_genes.as_mut_ptr().add(point),
-                len - point,
-            );
+    child1.fix(graph);
+    child2.fix(graph);
+
+    if local_search {
+        child1.remove_redundancy(graph);
+        child2.remove_redundancy(graph);
+    }
+
+    (child1, child2)
+}
+
+/// Two-point crossover: the children exchange the genes between two random cut points.
+#[derive(Clone, Debug)]
+pub struct TwoPoint {
+    crossover_rate: f64,
+    local_search: bool,
+}
+
+rate_constructors!(TwoPoint);
+
+impl Crossover for TwoPoint {
+    fn crossover(
+        &self,
+        parent1: &Chromosome,
+        parent2: &Chromosome,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
+    ) -> (Chromosome, Chromosome) {
+        let mut child1_genes = parent1.genes().to_vec();
+        let mut child2_genes = parent2.genes().to_vec();
+        if !rng.gen_bool(self.crossover_rate) {
+            return (Chromosome::new(child1_genes), Chromosome::new(child2_genes));
+        }
+
+        // Os pontos de corte ficam em 0..=len, então o segmento trocado pode ser vazio.
+        let len = child1_genes.len();
+        let a = rng.gen_range(0..=len);
+        let b = rng.gen_range(0..=len);
+        let (start, end) = (a.min(b), a.max(b));
+        child1_genes[start..end].swap_with_slice(&mut child2_genes[start..end]);
+
+        offspring(child1_genes, child2_genes, graph, self.local_search)
+    }
+}
+
+/// Uniform crossover: each gene of the first child comes from either parent with equal
+/// probability, and the second child takes the gene of the other parent.
+#[derive(Clone, Debug)]
+pub struct Uniform {
+    crossover_rate: f64,
+    local_search: bool,
+}
+
+rate_constructors!(Uniform);
+
+impl Crossover for Uniform {
+    fn crossover(
+        &self,
+        parent1: &Chromosome,
+        parent2: &Chromosome,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
+    ) -> (Chromosome, Chromosome) {
+        let mut child1_genes = parent1.genes().to_vec();
+        let mut child2_genes = parent2.genes().to_vec();
+        if !rng.gen_bool(self.crossover_rate) {
+            return (Chromosome::new(child1_genes), Chromosome::new(child2_genes));
+        }
+
+        for (gene1, gene2) in child1_genes.iter_mut().zip(child2_genes.iter_mut()) {
+            if rng.gen_bool(0.5) {
+                std::mem::swap(gene1, gene2);
+            }
+        }
+
+        offspring(child1_genes, child2_genes, graph, self.local_search)
+    }
+}
+
+/// Neighborhood crossover: the children inherit closed neighborhoods `N[v]` as blocks.
+///
+/// Vertices are visited in random order. For each vertex `v`, the genes of `N[v]` that have
+/// not been inherited yet come from one parent, chosen at random, in the first child and from
+/// the other parent in the second one. A vertex and the neighbors that dominate it therefore
+/// tend to keep the labels they had together, which a cut on vertex indices does not preserve.
+#[derive(Clone, Debug)]
+pub struct Neighborhood {
+    crossover_rate: f64,
+    local_search: bool,
+}
+
+rate_constructors!(Neighborhood);
+
+impl Crossover for Neighborhood {
+    fn crossover(
+        &self,
+        parent1: &Chromosome,
+        parent2: &Chromosome,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
+    ) -> (Chromosome, Chromosome) {
+        let mut child1_genes = parent1.genes().to_vec();
+        let mut child2_genes = parent2.genes().to_vec();
+        if !rng.gen_bool(self.crossover_rate) {
+            return (Chromosome::new(child1_genes), Chromosome::new(child2_genes));
+        }
+
+        let len = child1_genes.len();
+        let mut order: Vec<usize> = (0..len).collect();
+        order.shuffle(rng);
+
+        // Cada gene é herdado uma única vez, pelo primeiro bloco N[v] que o contém.
+        let mut inherited = vec![false; len];
+        for v in order {
+            if inherited[v] {
+                continue;
+            }
+
+            // O primeiro filho começa como cópia de parent1; trocar o bloco o herda de parent2.
+            let from_parent2 = rng.gen_bool(0.5);
+            for &u in std::iter::once(&v).chain(graph.neighbors(v)) {
+                if !inherited[u] {
+                    inherited[u] = true;
+                    if from_parent2 {
+                        std::mem::swap(&mut child1_genes[u], &mut child2_genes[u]);
+                    }
+                }
+            }
+        }
+
+        offspring(child1_genes, child2_genes, graph, self.local_search)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::{
+        csr::test_graphs::{path, random_connected},
+        genetic::{h1, h2, h3, h4, Heuristic},
+    };
+
+    fn operators(rate: f64) -> [(&'static str, Box<dyn Crossover>); 4] {
+        [
+            ("single-point", Box::new(SinglePoint::new(rate))),
+            ("two-point", Box::new(TwoPoint::new(rate))),
+            ("uniform", Box::new(Uniform::new(rate))),
+            (
+                "neighborhood",
+                Box::new(Neighborhood::new(rate).with_local_search(true)),
+            ),
+        ]
+    }
+
+    #[test]
+    fn children_have_the_length_of_their_parents() {
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        for order in [1, 2, 5, 17] {
+            let csr = CsrGraph::new(&path(order));
+            let parent1 = Chromosome::new(vec![2; order]);
+            let parent2 = Chromosome::new(vec![1; order]);
+
+            for (name, operator) in operators(1.0) {
+                let (child1, child2) = operator.crossover(&parent1, &parent2, &csr, &mut rng);
+                assert_eq!(child1.genes().len(), order, "{name}");
+                assert_eq!(child2.genes().len(), order, "{name}");
+            }
+        }
+    }
+
+    #[test]
+    fn zero_rate_returns_the_parents() {
+        let csr = CsrGraph::new(&path(6));
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        // Inválidos de propósito: sem cruzamento, os filhos não passam pelo reparo.
+        let parent1 = Chromosome::new(vec![0, 0, 2, 0, 0, 1]);
+        let parent2 = Chromosome::new(vec![1, 0, 0, 0, 2, 2]);
+
+        for (name, operator) in operators(0.0) {
+            for _ in 0..8 {
+                let (child1, child2) = operator.crossover(&parent1, &parent2, &csr, &mut rng);
+                assert_eq!(child1.genes(), parent1.genes(), "{name}");
+                assert_eq!(child2.genes(), parent2.genes(), "{name}");
+            }
         }
+    }
+
+    #[test]
+    fn children_of_zero_two_parents_are_valid() {
+        let mut rng = ChaCha8Rng::seed_from_u64(5);
+        let heuristics: [Heuristic; 4] = [h1, h2, h3, h4];
+
+        for _ in 0..50 {
+            let order = rng.gen_range(2..30);
+            let graph = random_connected(order, 0.1, &mut rng);
+            let csr = CsrGraph::new(&graph);
+            // Trocar 1 por 2 mantém a rotulação válida, e sem rótulos 1 o reparo só sobe
+            // vizinhos 0.
+            let mut parent = || {
+                let genes = heuristics[rng.gen_range(0..4)](&csr, &mut rng)
+                    .genes()
+                    .iter()
+                    .map(|&gene| if gene == 1 { 2 } else { gene })
+                    .collect();
+                Chromosome::new(genes)
+            };
+            let (parent1, parent2) = (parent(), parent());
+
+            for (name, operator) in operators(1.0) {
+                let (child1, child2) = operator.crossover(&parent1, &parent2, &csr, &mut rng);
+                assert!(child1.is_valid(&graph), "{name}: {child1}");
+                assert!(child2.is_valid(&graph), "{name}: {child2}");
+            }
+        }
+    }
+
+    #[test]
+    fn children_of_any_heuristic_are_valid() {
+        let mut rng = ChaCha8Rng::seed_from_u64(5);
+        let heuristics: [Heuristic; 4] = [h1, h2, h3, h4];
+
+        for _ in 0..50 {
+            let order = rng.gen_range(2..30);
+            let graph = random_connected(order, 0.1, &mut rng);
+            let csr = CsrGraph::new(&graph);
+            let parent1 = heuristics[rng.gen_range(0..4)](&csr, &mut rng);
+            let parent2 = heuristics[rng.gen_range(0..4)](&csr, &mut rng);
+
+            for (name, operator) in operators(1.0) {
+                let (child1, child2) = operator.crossover(&parent1, &parent2, &csr, &mut rng);
+                assert!(child1.is_valid(&graph), "{name}: {child1}");
+                assert!(child2.is_valid(&graph), "{name}: {child2}");
+            }
+        }
+    }
+
+    #[test]
+    fn single_point_copies_single_gene_parents() {
+        let csr = CsrGraph::new(&path(1));
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let (parent1, parent2) = (Chromosome::new(vec![1]), Chromosome::new(vec![2]));
+
+        let (child1, child2) = SinglePoint::new(1.0).crossover(&parent1, &parent2, &csr, &mut rng);
+
+        assert_eq!(child1.genes(), [1]);
+        assert_eq!(child2.genes(), [2]);
+    }
 
-        let mut child1 = Chromosome::new(child1_genes);
-        let mut child2 = Chromosome::new(child2_genes);
+    #[test]
+    fn single_point_cuts_two_gene_parents_in_the_middle() {
+        let csr = CsrGraph::new(&path(2));
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let (parent1, parent2) = (Chromosome::new(vec![1, 2]), Chromosome::new(vec![2, 1]));
 
-        child1.fix(graph);
-        child2.fix(graph);
+        for _ in 0..16 {
+            let (child1, child2) =
+                SinglePoint::new(1.0).crossover(&parent1, &parent2, &csr, &mut rng);
 
-        (child1, child2)
+            assert_eq!(child1.genes(), [1, 1]);
+            assert_eq!(child2.genes(), [2, 2]);
+            assert_eq!(child1.fitness(), 2);
+            assert_eq!(child2.fitness(), 4);
+        }
+    }
+
+    #[test]
+    fn single_point_repairs_two_gene_children() {
+        let graph = path(2);
+        let csr = CsrGraph::new(&graph);
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let (parent1, parent2) = (Chromosome::new(vec![0, 2]), Chromosome::new(vec![2, 0]));
+
+        let (child1, child2) = SinglePoint::new(1.0).crossover(&parent1, &parent2, &csr, &mut rng);
+
+        assert!(child1.is_valid(&graph));
+        assert!(child2.is_valid(&graph));
+    }
+
+    #[test]
+    fn single_point_keeps_parents_without_crossover() {
+        let csr = CsrGraph::new(&path(4));
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let parent1 = Chromosome::new(vec![0, 2, 2, 0]);
+        let parent2 = Chromosome::new(vec![1, 1, 1, 1]);
+
+        let (child1, child2) = SinglePoint::new(0.0).crossover(&parent1, &parent2, &csr, &mut rng);
+
+        assert_eq!(child1.genes(), parent1.genes());
+        assert_eq!(child2.genes(), parent2.genes());
+    }
+
+    #[test]
+    fn single_point_children_exchange_a_suffix() {
+        let csr = CsrGraph::new(&path(8));
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let parent1 = Chromosome::new(vec![1; 8]);
+        let parent2 = Chromosome::new(vec![2; 8]);
+
+        for _ in 0..32 {
+            let (child1, child2) =
+                SinglePoint::new(1.0).crossover(&parent1, &parent2, &csr, &mut rng);
+
+            // Todos os filhos já são válidos, então o reparo não esconde o ponto de corte.
+            let point = child1.genes().iter().position(|&g| g == 2).unwrap();
+            assert!((1..8).contains(&point));
+            assert!(child1.genes()[..point].iter().all(|&g| g == 1));
+            assert!(child1.genes()[point..].iter().all(|&g| g == 2));
+            assert!(child2.genes()[..point].iter().all(|&g| g == 2));
+            assert!(child2.genes()[point..].iter().all(|&g| g == 1));
+        }
     }
 }
@@ -1,15 +1,51 @@
-use kambo_graph::{graphs::simple::UndirectedGraph, Graph, GraphMut};
-use rand::seq::IteratorRandom;
+use rand::{seq::SliceRandom, RngCore};
 
 use super::chromosome::Chromosome;
+use crate::csr::{CsrGraph, DegreeBuckets};
 
 /// Aliases to representation of a Heuristic
-pub type Heuristic = fn(&UndirectedGraph<usize>) -> Chromosome;
+pub type Heuristic = fn(&CsrGraph, &mut dyn RngCore) -> Chromosome;
+
+/// Passos 5 a 7 de `h1`–`h4`: o primeiro vizinho recebe 1, os demais 0, e `v` e seus
+/// vizinhos são removidos de `h`.
+fn label_and_remove(h: &mut DegreeBuckets, genes: &mut [u8], v: usize, neighbors: &[usize]) {
+    if let Some((&first, rest)) = neighbors.split_first() {
+        genes[first] = 1;
+        for &w in rest {
+            genes[w] = 0;
+        }
+    }
+
+    h.remove(v);
+    for &neighbor in neighbors {
+        h.remove(neighbor);
+    }
+}
+
+/// Passos 8 a 12 de `h1`–`h3`: cada vértice isolado de `h` recebe 1 e, se não tiver vizinho
+/// com f = 1 no grafo original, um vizinho com f = 0 passa a 1. Depois sai de `h`.
+fn label_isolated(graph: &CsrGraph, h: &mut DegreeBuckets, genes: &mut [u8]) {
+    for z in h.isolated() {
+        genes[z] = 1;
+        let neighbors = graph.neighbors(z);
+
+        // Verifica se `z` tem vizinhos no grafo original com f = 1.
+        if !neighbors.iter().any(|&n| genes[n] == 1) {
+            if let Some(&first) = neighbors.iter().find(|&&n| genes[n] == 0) {
+                genes[first] = 1;
+            }
+        }
+
+        // Passo 12: Remove o vértice `z` do grafo `h`.
+        h.remove(z);
+    }
+}
 
 /// A heuristic function to generate a `Chromosome` using a randomized approach.
 ///
 /// # Arguments
-/// - `graph`: A reference to the undirected graph (`UnGraph`) for which the chromosome is generated.
+/// - `graph`: The graph (`CsrGraph`) for which the chromosome is generated.
+/// - `rng`: The random number generator driving the random choices.
 ///
 /// # Returns
 /// - A `Chromosome` where genes are assigned based on the following procedure:
@@ -18,66 +54,27 @@ pub type Heuristic = fn(&UndirectedGraph<usize>) -> Chromosome;
 ///   - Remaining neighbors are labeled `0`.
 ///   - Isolated vertices are handled separately and assigned labels to satisfy constraints.
 #[must_use]
-pub fn h1(graph: &UndirectedGraph<usize>) -> Chromosome {
+pub fn h1(graph: &CsrGraph, rng: &mut dyn RngCore) -> Chromosome {
     // Inicializa um vetor de genes com valores 0.
     // O tamanho do vetor é igual ao número de vértices no grafo.
     let mut genes = vec![0u8; graph.order()];
 
-    // Faz uma cópia do grafo original para ser manipulado sem alterar o original.
-    let mut h = graph.clone();
+    // Subgrafo h, que encolhe à medida que os vértices são removidos, sem copiar o grafo.
+    let mut h = DegreeBuckets::new(graph);
 
-    // Cria um gerador de números aleatórios para escolher vértices aleatoriamente.
-    let mut rng = rand::thread_rng();
-
-    // Enquanto o grafo h ainda tiver vértices...
-    while let Some(v) = h.vertices().choose(&mut rng).copied() {
+    // Enquanto o grafo h ainda tiver vértices... (o vértice é sorteado com o gerador recebido)
+    while let Some(&v) = h.vertices().choose(rng) {
         // Passo 4: Define f(v) = 2, marcando o vértice v com a cor 2.
         genes[v] = 2;
 
         // Obtém os vizinhos de v no grafo `h`.
-        let neighbors: Vec<usize> = h
-            .neighbors(&v)
-            .map(|n| n.copied().collect())
-            .unwrap_or_default();
-
-        // Passo 5: Se v tem vizinhos, escolha um (o primeiro da lista) e defina f(u) = 1.
-        if let Some(first_neighbor) = neighbors.first() {
-            genes[*first_neighbor] = 1;
-
-            // Passo 6: Para os demais vizinhos de v, define f(w) = 0.
-            for w in neighbors.iter().skip(1) {
-                genes[*w] = 0;
-            }
-        }
+        let neighbors: Vec<usize> = h.neighbors(v).collect();
 
-        // Passo 7: Remove o vértice `v` e seus vizinhos do grafo `h`.
-        let _ = h.remove_vertex(&v);
-        for neighbor in neighbors {
-            let _ = h.remove_vertex(&neighbor);
-        }
+        // Passos 5 a 7: rotula os vizinhos e remove `v` e seus vizinhos de `h`.
+        label_and_remove(&mut h, &mut genes, v, &neighbors);
 
         // Passo 8: Enquanto houver vértices isolados em h...
-        let isolated_vertices = h.get_isolated_vertices();
-        for z in isolated_vertices {
-            // Caso contrário, define f(z) = 1.
-            genes[z] = 1;
-            let has_neighbor_with_1 = graph
-                .neighbors(&z)
-                .is_some_and(|mut neighbors| neighbors.any(|n| genes[*n] == 1));
-
-            // Verifica se `z` tem vizinhos no grafo original com f = 1.
-            if !has_neighbor_with_1 {
-                // Se não há vizinhos com f = 1, escolhe um vizinho com f = 0 e define f = 1.
-                if let Some(mut neighbors) = graph.neighbors(&z) {
-                    if let Some(first) = neighbors.find(|&n| genes[*n] == 0) {
-                        genes[*first] = 1;
-                    }
-                }
-            }
-
-            // Passo 12: Remove o vértice `z` do grafo `h`.
-            let _ = h.remove_vertex(&z);
-        }
+        label_isolated(graph, &mut h, &mut genes);
     }
 
     // Retorna a solução como um Chromosome, encapsulando o vetor de genes.
@@ -87,13 +84,14 @@ pub fn h1(graph: &UndirectedGraph<usize>) -> Chromosome {
 /// A heuristic function to generate a `Chromosome` using a vertex degree-based approach.
 ///
 /// # Overview
-/// This heuristic assigns labels to vertices in an undirected graph (`UnGraph`) by prioritizing
+/// This heuristic assigns labels to vertices in a graph (`CsrGraph`) by prioritizing
 /// vertices with the highest degree (number of neighbors). This strategy aims to maximize the
 /// impact of the labels on highly connected vertices, which are likely to influence the overall
 /// graph structure.
 ///
 /// # Arguments
-/// - `graph`: A reference to the undirected graph (`UnGraph`) for which the chromosome is generated.
+/// - `graph`: The graph (`CsrGraph`) for which the chromosome is generated.
+/// - `_rng`: Unused, the heuristic is deterministic.
 ///
 /// # Returns
 /// - A `Chromosome` where genes are assigned based on the following procedure:
@@ -114,62 +112,27 @@ pub fn h1(graph: &UndirectedGraph<usize>) -> Chromosome {
 /// This heuristic is similar to `h1`, but it prioritizes vertices with the highest degree
 /// during the selection process, aiming to optimize the influence of the assigned labels.
 #[must_use]
-pub fn h2(graph: &UndirectedGraph<usize>) -> Chromosome {
+pub fn h2(graph: &CsrGraph, _rng: &mut dyn RngCore) -> Chromosome {
     // Inicializa um vetor de genes com valores 0.
     // O tamanho do vetor é igual ao número de vértices no grafo.
     let mut genes = vec![0u8; graph.order()];
 
-    // Faz uma cópia do grafo original para ser manipulado sem alterar o original.
-    let mut h = graph.clone();
+    // Subgrafo h, que encolhe à medida que os vértices são removidos, sem copiar o grafo.
+    let mut h = DegreeBuckets::new(graph);
 
     // Enquanto o grafo h ainda tiver vértices... (Já captura o v = vértice de maior grau do grafo)
-    while let Some(v) = h.vertices().max_by_key(|&vertex| h.degree(vertex)).copied() {
+    while let Some(v) = h.max_degree_vertex() {
         // Passo 4: Define f(v) = 2, marcando o vértice v com a cor 2.
         genes[v] = 2;
 
         // Obtém os vizinhos de v no grafo `h`.
-        let neighbors: Vec<usize> = h
-            .neighbors(&v)
-            .map(|n| n.copied().collect())
-            .unwrap_or_default();
-
-        // Passo 5: Se v tem vizinhos, escolha um (o primeiro da lista) e defina f(u) = 1.
-        if let Some(first_neighbor) = neighbors.first() {
-            genes[*first_neighbor] = 1;
-
-            // Passo 6: Para os demais vizinhos de v, define f(w) = 0.
-            for w in neighbors.iter().skip(1) {
-                genes[*w] = 0;
-            }
-        }
+        let neighbors: Vec<usize> = h.neighbors(v).collect();
 
-        // Passo 7: Remove o vértice `v` e seus vizinhos do grafo `h`.
-        let _ = h.remove_vertex(&v);
-        for neighbor in neighbors {
-            let _ = h.remove_vertex(&neighbor);
-        }
+        // Passos 5 a 7: rotula os vizinhos e remove `v` e seus vizinhos de `h`.
+        label_and_remove(&mut h, &mut genes, v, &neighbors);
 
         // Passo 8: Enquanto houver vértices isolados em h...
-        let isolated_vertices = h.get_isolated_vertices();
-        for z in isolated_vertices {
-            genes[z] = 1;
-            let has_neighbor_with_1 = graph
-                .neighbors(&z)
-                .is_some_and(|mut neighbors| neighbors.any(|n| genes[*n] == 1));
-
-            // Verifica se `z` tem vizinhos no grafo original com f = 1.
-            if !has_neighbor_with_1 {
-                // Se não há vizinhos com f = 1, escolhe um vizinho com f = 0 e define f = 1.
-                if let Some(mut neighbors) = graph.neighbors(&z) {
-                    if let Some(first) = neighbors.find(|&n| genes[*n] == 0) {
-                        genes[*first] = 1;
-                    }
-                }
-            }
-
-            // Passo 12: Remove o vértice `z` do grafo `h`.
-            let _ = h.remove_vertex(&z);
-        }
+        label_isolated(graph, &mut h, &mut genes);
     }
 
     // Retorna a solução como um Chromosome, encapsulando o vetor de genes.
@@ -179,12 +142,13 @@ pub fn h2(graph: &UndirectedGraph<usize>) -> Chromosome {
 /// A heuristic function to generate a `Chromosome` using a degree-based and neighbor-priority approach.
 ///
 /// # Overview
-/// This heuristic assigns labels to vertices in an undirected graph (`UnGraph`) by prioritizing vertices with
+/// This heuristic assigns labels to vertices in a graph (`CsrGraph`) by prioritizing vertices with
 /// the highest degree and further refining the selection of neighbors based on their degrees. The goal is to
 /// maximize the influence of labels while ensuring constraints are satisfied.
 ///
 /// # Arguments
-/// - `graph`: A reference to the undirected graph (`UnGraph`) for which the chromosome is generated.
+/// - `graph`: The graph (`CsrGraph`) for which the chromosome is generated.
+/// - `_rng`: Unused, the heuristic is deterministic.
 ///
 /// # Returns
 /// - A `Chromosome` where genes are assigned based on the following procedure:
@@ -205,66 +169,30 @@ pub fn h2(graph: &UndirectedGraph<usize>) -> Chromosome {
 /// - This heuristic refines the approach of `h2` by introducing a sorting step to prioritize neighbors with higher degrees.
 /// - It is particularly useful in graphs where the connectivity of neighbors significantly influences the solution.
 #[must_use]
-pub fn h3(graph: &UndirectedGraph<usize>) -> Chromosome {
+pub fn h3(graph: &CsrGraph, _rng: &mut dyn RngCore) -> Chromosome {
     // Inicializa um vetor de genes com valores 0.
     // O tamanho do vetor é igual ao número de vértices no grafo.
     let mut genes = vec![0u8; graph.order()];
 
-    // Faz uma cópia do grafo original para ser manipulado sem alterar o original.
-    let mut h = graph.clone();
+    // Subgrafo h, que encolhe à medida que os vértices são removidos, sem copiar o grafo.
+    let mut h = DegreeBuckets::new(graph);
 
     // Enquanto o grafo h ainda tiver vértices... (Já captura o v = vértice de maior grau do grafo)
-    while let Some(v) = h.vertices().max_by_key(|&vertex| h.degree(vertex)).copied() {
+    while let Some(v) = h.max_degree_vertex() {
         // Passo 4: Define f(v) = 2, marcando o vértice v com a cor 2.
         genes[v] = 2;
 
         // Obtém os vizinhos de v no grafo `h`.
-        let mut neighbors: Vec<usize> = h
-            .neighbors(&v)
-            .map(|n| n.copied().collect())
-            .unwrap_or_default();
+        let mut neighbors: Vec<usize> = h.neighbors(v).collect();
 
         // Ordena os vizinhos de forma decrescente pelo grau
-        neighbors.sort_by(|&a, &b| h.degree(&b).cmp(&h.degree(&a)));
-
-        // Passo 5: Se v tem vizinhos, escolha um (o primeiro da lista, ou seja, o com maior grau) e defina f(u) = 1.
-        if let Some(first_neighbor) = neighbors.first() {
-            genes[*first_neighbor] = 1;
-
-            // Passo 6: Para os demais vizinhos de v, define f(w) = 0.
-            for w in neighbors.iter().skip(1) {
-                genes[*w] = 0;
-            }
-        }
+        neighbors.sort_by_key(|&n| std::cmp::Reverse(h.degree(n)));
 
-        // Passo 7: Remove o vértice `v` e seus vizinhos do grafo `h`.
-        let _ = h.remove_vertex(&v);
-        for neighbor in neighbors {
-            let _ = h.remove_vertex(&neighbor);
-        }
+        // Passos 5 a 7: o vizinho de maior grau recebe 1, e `v` e seus vizinhos saem de `h`.
+        label_and_remove(&mut h, &mut genes, v, &neighbors);
 
         // Passo 8: Enquanto houver vértices isolados em h...
-        let isolated_vertices = h.get_isolated_vertices();
-        for z in isolated_vertices {
-            // Caso contrário, define f(z) = 1.
-            genes[z] = 1;
-            let has_neighbor_with_1 = graph
-                .neighbors(&z)
-                .is_some_and(|mut neighbors| neighbors.any(|n| genes[*n] == 1));
-
-            // Verifica se `z` tem vizinhos no grafo original com f = 1.
-            if !has_neighbor_with_1 {
-                // Se não há vizinhos com f = 1, escolhe um vizinho com f = 0 e define f = 1.
-                if let Some(mut neighbors) = graph.neighbors(&z) {
-                    if let Some(first) = neighbors.find(|&n| genes[*n] == 0) {
-                        genes[*first] = 1;
-                    }
-                }
-            }
-
-            // Passo 12: Remove o vértice `z` do grafo `h`.
-            let _ = h.remove_vertex(&z);
-        }
+        label_isolated(graph, &mut h, &mut genes);
     }
 
     // Retorna a solução como um Chromosome, encapsulando o vetor de genes.
@@ -274,11 +202,12 @@ pub fn h3(graph: &UndirectedGraph<usize>) -> Chromosome {
 /// A heuristic function to generate a `Chromosome` using a degree-based and isolated vertex clustering approach.
 ///
 /// # Overview
-/// This heuristic assigns labels to vertices in an undirected graph (`UnGraph`) by prioritizing high-degree vertices
+/// This heuristic assigns labels to vertices in a graph (`CsrGraph`) by prioritizing high-degree vertices
 /// and clustering isolated vertices with common neighbors. It ensures all constraints are met while minimizing label violations.
 ///
 /// # Arguments
-/// - `graph`: A reference to the undirected graph (`UnGraph`) for which the chromosome is generated.
+/// - `graph`: The graph (`CsrGraph`) for which the chromosome is generated.
+/// - `_rng`: Unused, the heuristic is deterministic.
 ///
 /// # Returns
 /// - A `Chromosome` where genes are assigned based on the following procedure:
@@ -299,53 +228,35 @@ pub fn h3(graph: &UndirectedGraph<usize>) -> Chromosome {
 ///   into clusters based on their connections to common neighbors.
 /// - It is particularly useful for graphs with sparse regions or large numbers of isolated vertices.
 #[must_use]
-pub fn h4(graph: &UndirectedGraph<usize>) -> Chromosome {
+pub fn h4(graph: &CsrGraph, _rng: &mut dyn RngCore) -> Chromosome {
     // Inicializa um vetor de genes com valores 0.
     // O tamanho do vetor é igual ao número de vértices no grafo.
     let mut genes = vec![0u8; graph.order()];
 
-    // Faz uma cópia do grafo original para ser manipulado sem alterar o original.
-    let mut h = graph.clone();
+    // Subgrafo h, que encolhe à medida que os vértices são removidos, sem copiar o grafo.
+    let mut h = DegreeBuckets::new(graph);
+
+    // Marca os vértices do conjunto S de isolados da iteração atual.
+    let mut in_isolated = vec![false; graph.order()];
 
     // Enquanto o grafo h ainda tiver vértices... (Já captura o v = vértice de maior grau do grafo)
-    while let Some(v) = h.vertices().max_by_key(|&vertex| h.degree(vertex)).copied() {
+    while let Some(v) = h.max_degree_vertex() {
         // Passo 4: Define f(v) = 2, marcando o vértice v com a cor 2.
         genes[v] = 2;
 
         // Obtém os vizinhos de v no grafo `h`.
-        let mut neighbors: Vec<usize> = h
-            .neighbors(&v)
-            .map(|n| n.copied().collect())
-            .unwrap_or_default();
+        let mut neighbors: Vec<usize> = h.neighbors(v).collect();
 
         // Ordena os vizinhos de forma decrescente pelo grau
-        neighbors.sort_by(|&a, &b| h.degree(&b).cmp(&h.degree(&a)));
+        neighbors.sort_by_key(|&n| std::cmp::Reverse(h.degree(n)));
 
-        // Passo 5: Se v tem vizinhos, escolha um (o primeiro da lista, ou seja, o com maior grau) e defina f(u) = 1.
-        if let Some(first_neighbor) = neighbors.first() {
-            genes[*first_neighbor] = 1;
-
-            // Passo 6: Para os demais vizinhos de v, define f(w) = 0.
-            for w in neighbors.iter().skip(1) {
-                genes[*w] = 0;
-            }
-        }
-
-        // Passo 7: Remove o vértice `v` e seus vizinhos do grafo `h`.
-        let _ = h.remove_vertex(&v);
-        for neighbor in neighbors {
-            let _ = h.remove_vertex(&neighbor);
-        }
+        // Passos 5 a 7: o vizinho de maior grau recebe 1, e `v` e seus vizinhos saem de `h`.
+        label_and_remove(&mut h, &mut genes, v, &neighbors);
 
         // Passo 8-14: Processa vértices isolados
         loop {
             // Encontra vértices isolados em H
-            let isolated: Vec<usize> = h
-                .vertices()
-                .filter(|&v| h.degree(v).unwrap_or(0) == 0)
-                .copied()
-                .collect();
-
+            let isolated = h.isolated();
             if isolated.is_empty() {
                 break;
             }
@@ -353,49 +264,35 @@ pub fn h4(graph: &UndirectedGraph<usize>) -> Chromosome {
             // Encontra os vizinhos dos vértices isolados no grafo original
             let mut ns: Vec<usize> = Vec::new();
             for &s in &isolated {
-                ns.extend(
-                    graph
-                        .neighbors(&s)
-                        .map(|n| n.copied().collect::<Vec<_>>())
-                        .unwrap_or_default(),
-                );
+                in_isolated[s] = true;
+                ns.extend_from_slice(graph.neighbors(s));
             }
             ns.sort_unstable();
             ns.dedup();
 
-            // Para cada vizinho z em N(S)
+            // Para cada vizinho z em N(S), f(z) = 2
             for &z in &ns {
-                // Conta quantos vizinhos z tem em S
-                let isolated_neighbors = isolated
+                genes[z] = 2;
+
+                // Se z tem 2 ou mais vizinhos em S, seus vizinhos em S recebem 0
+                let isolated_neighbors = graph
+                    .neighbors(z)
                     .iter()
-                    .filter(|&&s| graph.contains_edge(&z, &s))
+                    .filter(|&&s| in_isolated[s])
                     .count();
-
                 if isolated_neighbors >= 2 {
-                    // Se z tem 2 ou mais vizinhos em S, define f(z) = 2
-                    genes[z] = 2;
-                    // E seus vizinhos em S recebem 0
-                    for &s in &isolated {
-                        if graph.contains_edge(&z, &s) {
+                    for &s in graph.neighbors(z) {
+                        if in_isolated[s] {
                             genes[s] = 0;
                         }
                     }
-                } else {
-                    // Caso contrário, também recebe 2
-                    genes[z] = 2;
-                }
-            }
-
-            // Pinta com 0 os vértices restantes em S
-            for &s in &isolated {
-                if genes[s] == 0 {
-                    genes[s] = 0;
                 }
             }
 
             // Remove todos os vértices de S do grafo H
             for s in isolated {
-                let _ = h.remove_vertex(&s);
+                in_isolated[s] = false;
+                h.remove(s);
             }
         }
     }
@@ -411,13 +308,88 @@ pub fn h4(graph: &UndirectedGraph<usize>) -> Chromosome {
 /// It serves as a baseline or trivial solution, ensuring all vertices satisfy a minimum labeling constraint.
 ///
 /// # Arguments
-/// - `graph`: A reference to the undirected graph (`UnGraph`) for which the chromosome is generated.
+/// - `graph`: The graph (`CsrGraph`) for which the chromosome is generated.
+/// - `_rng`: Unused, the heuristic is deterministic.
 ///
 /// # Returns
 /// - A `Chromosome` where all genes are assigned the label `1`.
 #[must_use]
-pub fn h5(graph: &UndirectedGraph<usize>) -> Chromosome {
+pub fn h5(graph: &CsrGraph, _rng: &mut dyn RngCore) -> Chromosome {
     // Cria um vetor de genes com todos os vértices rotulados com valor 1;
     let genes: Vec<u8> = vec![1; graph.order()];
     Chromosome::new(genes)
 }
+
+#[cfg(test)]
+mod tests {
+    use kambo_graph::{graphs::simple::UndirectedGraph, GraphMut};
+    use rand::prelude::*;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::csr::test_graphs::random_connected;
+
+    fn graph(order: usize, edges: &[(usize, usize)]) -> UndirectedGraph<usize> {
+        let mut graph = UndirectedGraph::<usize>::new_undirected();
+        for v in 0..order {
+            graph.add_vertex(v).unwrap();
+        }
+        for (u, v) in edges {
+            graph.add_edge(u, v).unwrap();
+        }
+        graph
+    }
+
+    /// Small graphs without isolated vertices: paths, cycles, a star, a complete graph, two
+    /// components, and random graphs around a Hamiltonian path.
+    fn samples(rng: &mut impl Rng) -> Vec<UndirectedGraph<usize>> {
+        let path = |order: usize| (1..order).map(|v| (v - 1, v)).collect::<Vec<_>>();
+        let cycle = |order: usize| {
+            let mut edges = path(order);
+            edges.push((order - 1, 0));
+            edges
+        };
+        let complete: Vec<_> = (0..5)
+            .flat_map(|u| (u + 1..5).map(move |v| (u, v)))
+            .collect();
+
+        let mut samples = vec![
+            graph(2, &path(2)),
+            graph(3, &path(3)),
+            graph(7, &path(7)),
+            graph(3, &cycle(3)),
+            graph(8, &cycle(8)),
+            graph(6, &[(0, 1), (0, 2), (0, 3), (0, 4), (0, 5)]),
+            graph(5, &complete),
+            graph(5, &[(0, 1), (2, 3), (3, 4)]),
+        ];
+        for _ in 0..20 {
+            let order = rng.gen_range(4..30);
+            samples.push(random_connected(order, 0.1, rng));
+        }
+        samples
+    }
+
+    #[test]
+    fn every_heuristic_builds_a_valid_labeling() {
+        let mut rng = ChaCha8Rng::seed_from_u64(17);
+        let heuristics: [(&str, Heuristic); 5] =
+            [("h1", h1), ("h2", h2), ("h3", h3), ("h4", h4), ("h5", h5)];
+
+        for graph in samples(&mut rng) {
+            let csr = CsrGraph::new(&graph);
+            for (name, heuristic) in heuristics {
+                let chromosome = heuristic(&csr, &mut rng);
+                assert_eq!(chromosome.genes().len(), csr.order());
+                assert_eq!(
+                    chromosome.violations(&graph),
+                    [],
+                    "{name} on {:?}: {chromosome}",
+                    (0..csr.order())
+                        .map(|v| csr.neighbors(v))
+                        .collect::<Vec<_>>()
+                );
+            }
+        }
+    }
+}
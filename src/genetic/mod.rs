@@ -4,6 +4,9 @@ pub mod chromosome;
 /// Crossover
 pub mod crossover;
 
+/// Mutation
+pub mod mutation;
+
 /// Heuristics to generate initial population
 pub mod heuristics;
 
@@ -13,8 +16,20 @@ pub mod selection;
 ///Population
 pub mod population;
 
-pub use chromosome::Chromosome;
-pub use crossover::{Crossover, SinglePoint};
+/// Termination criteria
+pub mod termination;
+
+/// Checkpoints of running trials
+pub mod checkpoint;
+
+pub use checkpoint::Checkpoint;
+pub use chromosome::{Chromosome, ParseChromosomeError, Violation};
+pub use crossover::{Crossover, Neighborhood, SinglePoint, TwoPoint, Uniform};
 pub use heuristics::{h1, h2, h3, h4, h5, Heuristic};
-pub use population::Population;
-pub use selection::{KTournament, Selection};
+pub use mutation::{DemoteTwo, Mutation, RandomRelabel, SwapLabels};
+pub use population::{FitnessStats, GenerationStats, Population, Replacement};
+pub use selection::{KTournament, LinearRank, Roulette, Selection, StochasticUniversal};
+pub use termination::{
+    AllOf, AnyOf, DiversityCollapse, EvaluationBudget, MaxGenerations, Progress, Stagnation,
+    TargetFitness, Termination, TimeLimit,
+};
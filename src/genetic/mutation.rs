@@ -0,0 +1,215 @@
+use rand::prelude::*;
+
+use super::chromosome::Chromosome;
+use crate::csr::CsrGraph;
+
+/// Trait defining mutation operations
+pub trait Mutation {
+    /// Mutates a chromosome in place, then repairs it with [`Chromosome::fix`], so on a graph
+    /// without isolated vertices the result is always a valid total Roman dominating function.
+    ///
+    /// Implementations decide, according to their mutation rate, whether the chromosome
+    /// is changed at all. Every random choice is drawn from `rng`.
+    fn mutate(&self, chromosome: &mut Chromosome, graph: &CsrGraph, rng: &mut dyn RngCore);
+}
+
+fn check_rate(mutation_rate: f64) {
+    assert!(
+        (0.0..=1.0).contains(&mutation_rate),
+        "Mutation probability must be between 0 and 1"
+    );
+}
+
+/// Mutation that gives a random vertex one of the two labels it does not have.
+#[derive(Clone, Debug)]
+pub struct RandomRelabel {
+    mutation_rate: f64,
+}
+
+impl RandomRelabel {
+    /// Creates a new instance with a specified mutation rate.
+    ///
+    /// # Parameters
+    /// - `mutation_rate: f64`: Probability of mutating a chromosome, in `[0.0, 1.0]`.
+    ///
+    /// # Panics
+    /// - If `mutation_rate` is outside the range `[0.0, 1.0]`.
+    #[inline]
+    #[must_use]
+    pub fn new(mutation_rate: f64) -> Self {
+        check_rate(mutation_rate);
+        Self { mutation_rate }
+    }
+}
+
+impl Mutation for RandomRelabel {
+    fn mutate(&self, chromosome: &mut Chromosome, graph: &CsrGraph, rng: &mut dyn RngCore) {
+        let len = chromosome.genes().len();
+        if len == 0 || !rng.gen_bool(self.mutation_rate) {
+            return;
+        }
+
+        let vertex = rng.gen_range(0..len);
+        let label = (chromosome.genes()[vertex] + rng.gen_range(1..=2)) % 3;
+        chromosome.set_gene(vertex, label, graph);
+        chromosome.fix(graph);
+    }
+}
+
+/// Mutation that swaps the labels of two random vertices.
+#[derive(Clone, Debug)]
+pub struct SwapLabels {
+    mutation_rate: f64,
+}
+
+impl SwapLabels {
+    /// Creates a new instance with a specified mutation rate.
+    ///
+    /// # Parameters
+    /// - `mutation_rate: f64`: Probability of mutating a chromosome, in `[0.0, 1.0]`.
+    ///
+    /// # Panics
+    /// - If `mutation_rate` is outside the range `[0.0, 1.0]`.
+    #[inline]
+    #[must_use]
+    pub fn new(mutation_rate: f64) -> Self {
+        check_rate(mutation_rate);
+        Self { mutation_rate }
+    }
+}
+
+impl Mutation for SwapLabels {
+    fn mutate(&self, chromosome: &mut Chromosome, graph: &CsrGraph, rng: &mut dyn RngCore) {
+        let len = chromosome.genes().len();
+        if len < 2 || !rng.gen_bool(self.mutation_rate) {
+            return;
+        }
+
+        let u = rng.gen_range(0..len);
+        let v = rng.gen_range(0..len);
+        let (label_u, label_v) = (chromosome.genes()[u], chromosome.genes()[v]);
+        if label_u == label_v {
+            return;
+        }
+
+        chromosome.set_gene(u, label_v, graph);
+        chromosome.set_gene(v, label_u, graph);
+        chromosome.fix(graph);
+    }
+}
+
+/// Mutation that demotes a random vertex labeled `2` to `1` or `0`, then repairs the chromosome.
+#[derive(Clone, Debug)]
+pub struct DemoteTwo {
+    mutation_rate: f64,
+}
+
+impl DemoteTwo {
+    /// Creates a new instance with a specified mutation rate.
+    ///
+    /// # Parameters
+    /// - `mutation_rate: f64`: Probability of mutating a chromosome, in `[0.0, 1.0]`.
+    ///
+    /// # Panics
+    /// - If `mutation_rate` is outside the range `[0.0, 1.0]`.
+    #[inline]
+    #[must_use]
+    pub fn new(mutation_rate: f64) -> Self {
+        check_rate(mutation_rate);
+        Self { mutation_rate }
+    }
+}
+
+impl Mutation for DemoteTwo {
+    fn mutate(&self, chromosome: &mut Chromosome, graph: &CsrGraph, rng: &mut dyn RngCore) {
+        if !rng.gen_bool(self.mutation_rate) {
+            return;
+        }
+
+        let Some(vertex) = chromosome
+            .genes()
+            .iter()
+            .enumerate()
+            .filter(|(_, &label)| label == 2)
+            .map(|(vertex, _)| vertex)
+            .choose(rng)
+        else {
+            return;
+        };
+
+        chromosome.set_gene(vertex, rng.gen_range(0..=1), graph);
+        chromosome.fix(graph);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::{
+        csr::test_graphs::random_connected,
+        genetic::{h1, h2, h3, h4, h5, Heuristic},
+    };
+
+    fn operators(rate: f64) -> [(&'static str, Box<dyn Mutation>); 3] {
+        [
+            ("random-relabel", Box::new(RandomRelabel::new(rate))),
+            ("swap", Box::new(SwapLabels::new(rate))),
+            ("demote-two", Box::new(DemoteTwo::new(rate))),
+        ]
+    }
+
+    #[test]
+    fn zero_rate_leaves_the_chromosome_unchanged() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let graph = random_connected(12, 0.1, &mut rng);
+        let csr = CsrGraph::new(&graph);
+        let original = h1(&csr, &mut rng);
+
+        for (name, operator) in operators(0.0) {
+            let mut chromosome = original.clone();
+            for _ in 0..20 {
+                operator.mutate(&mut chromosome, &csr, &mut rng);
+            }
+            assert_eq!(chromosome.genes(), original.genes(), "{name}");
+        }
+    }
+
+    #[test]
+    fn demote_two_without_twos_does_nothing() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let csr = CsrGraph::new(&random_connected(8, 0.1, &mut rng));
+        let mut chromosome = h5(&csr, &mut rng);
+
+        DemoteTwo::new(1.0).mutate(&mut chromosome, &csr, &mut rng);
+        assert_eq!(chromosome.genes(), [1; 8]);
+        assert!(!chromosome.was_repaired());
+    }
+
+    #[test]
+    fn mutated_chromosomes_are_repaired() {
+        let mut rng = ChaCha8Rng::seed_from_u64(9);
+        let heuristics: [Heuristic; 5] = [h1, h2, h3, h4, h5];
+
+        for _ in 0..50 {
+            let order = rng.gen_range(2..30);
+            let graph = random_connected(order, 0.1, &mut rng);
+            let csr = CsrGraph::new(&graph);
+            let original = heuristics[rng.gen_range(0..5)](&csr, &mut rng);
+
+            for (name, operator) in operators(1.0) {
+                let mut chromosome = original.clone();
+                operator.mutate(&mut chromosome, &csr, &mut rng);
+
+                assert_eq!(chromosome.genes().len(), order, "{name}");
+                assert_eq!(
+                    chromosome.fitness(),
+                    chromosome.genes().iter().map(|&g| usize::from(g)).sum(),
+                    "{name}"
+                );
+                assert!(chromosome.is_valid(&graph), "{name}: {chromosome}");
+            }
+        }
+    }
+}
@@ -1,6 +1,9 @@
-use kambo_graph::graphs::simple::UndirectedGraph;
+use std::io::{self, BufRead, Write};
 
-use super::{Chromosome, Crossover, Heuristic, Selection};
+use rand::RngCore;
+
+use super::{Chromosome, Crossover, Heuristic, Mutation, Selection};
+use crate::csr::CsrGraph;
 
 /// Represents a population of chromosomes for evolutionary algorithms.
 ///
@@ -11,10 +14,58 @@ use super::{Chromosome, Crossover, Heuristic, Selection};
 /// # Fields
 /// - `chromosomes: Vec<Chromosome>`: A vector containing the chromosomes in the population.
 /// - `size: usize`: The maximum size of the population.
+/// - `replacement: Replacement`: How offspring enter the population in `envolve`.
 #[derive(Clone)]
 pub struct Population {
     chromosomes: Vec<Chromosome>,
     size: usize,
+    replacement: Replacement,
+}
+
+/// Replacement strategy used by [`Population::envolve`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Replacement {
+    /// Offspring replace the whole generation, except for the `elitism` best chromosomes
+    /// (by `fitness()`), which carry over unchanged.
+    Generational {
+        /// Number of best chromosomes copied into the next generation.
+        elitism: usize,
+    },
+    /// Only `offspring` children are generated per call; each one replaces the current worst
+    /// chromosome of the population, unless it is worse than it.
+    SteadyState {
+        /// Number of children generated per call.
+        offspring: usize,
+    },
+}
+
+/// What one call to [`Population::envolve`] did.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GenerationStats {
+    /// Number of children generated, that is, of new chromosomes evaluated.
+    pub evaluations: usize,
+    /// Number of those children that had to be repaired by [`Chromosome::fix`].
+    pub repaired: usize,
+}
+
+/// Summary of the fitness values of a population.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FitnessStats {
+    /// Lowest fitness.
+    pub best: usize,
+    /// Highest fitness.
+    pub worst: usize,
+    /// Mean fitness.
+    pub mean: f64,
+    /// Median fitness, the mean of the two middle values for an even population size.
+    pub median: f64,
+}
+
+impl Default for Replacement {
+    /// Plain generational replacement, without elitism.
+    fn default() -> Self {
+        Self::Generational { elitism: 0 }
+    }
 }
 
 impl Population {
@@ -28,9 +79,10 @@ impl Population {
     /// - `size: usize`: The number of chromosomes to generate for the population.
     /// - `heuristics: Vec<Heuristic>`:
     ///   A vector of heuristic functions used to generate chromosomes.
-    ///   Each heuristic is a function of the form `fn(&UnGraph<u32, ()>) -> Chromosome`.
-    /// - `graph: &UnGraph<u32, ()>`:
-    ///   An undirected graph that represents the problem structure.
+    ///   Each heuristic is a function of the form `fn(&CsrGraph, &mut dyn RngCore) -> Chromosome`.
+    /// - `graph: &CsrGraph`:
+    ///   The adjacency of the graph that represents the problem structure.
+    /// - `rng: &mut dyn RngCore`: The random number generator passed to the heuristics.
     ///
     /// # Panics
     /// - If the `heuristics` vector is empty.
@@ -40,11 +92,17 @@ impl Population {
     /// - A new instance of `Population` with chromosomes generated by the heuristics.
     ///
     /// # Notes
-    /// - Chromosomes are adjusted using their `fix` method to ensure they satisfy
-    ///   problem-specific constraints.
+    /// - Chromosomes are kept exactly as the heuristics build them, without calling
+    ///   [`Chromosome::fix`]; [`Population::remove_redundancy`] can then make them locally
+    ///   minimal.
     #[inline]
     #[must_use]
-    pub fn new(size: usize, heuristics: &[Heuristic], graph: &UndirectedGraph<usize>) -> Self {
+    pub fn new(
+        size: usize,
+        heuristics: &[Heuristic],
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
+    ) -> Self {
         assert!(
             !heuristics.is_empty(),
             "At least one heuristic must be provided."
@@ -53,18 +111,64 @@ impl Population {
 
         for heuristic in heuristics {
             if chromosomes.len() < size {
-                let chromosome = heuristic(graph);
+                let chromosome = heuristic(graph, rng);
                 chromosomes.push(chromosome);
             }
         }
 
         let last_heuristic = *heuristics.last().unwrap();
         while chromosomes.len() < size {
-            let chromosome = last_heuristic(graph);
+            let chromosome = last_heuristic(graph, rng);
             chromosomes.push(chromosome);
         }
 
-        Self { chromosomes, size }
+        Self {
+            chromosomes,
+            size,
+            replacement: Replacement::default(),
+        }
+    }
+
+    /// Sets the replacement strategy used by [`Population::envolve`].
+    ///
+    /// # Parameters
+    /// - `replacement: Replacement`: The strategy. Elitism and offspring counts are clamped to
+    ///   the population size.
+    ///
+    /// # Returns
+    /// - The population with the new replacement strategy.
+    #[inline]
+    #[must_use]
+    pub fn with_replacement(mut self, replacement: Replacement) -> Self {
+        self.replacement = match replacement {
+            Replacement::Generational { elitism } => Replacement::Generational {
+                elitism: elitism.min(self.size),
+            },
+            Replacement::SteadyState { offspring } => Replacement::SteadyState {
+                offspring: offspring.min(self.size),
+            },
+        };
+        self
+    }
+
+    /// Applies [`Chromosome::remove_redundancy`] to every chromosome of the population.
+    ///
+    /// Called right after [`Population::new`], it turns the chromosomes built by the
+    /// heuristics into locally minimal labelings.
+    ///
+    /// # Parameters
+    /// - `graph: &CsrGraph`: The graph the chromosomes label.
+    pub fn remove_redundancy(&mut self, graph: &CsrGraph) {
+        for chromosome in &mut self.chromosomes {
+            chromosome.remove_redundancy(graph);
+        }
+    }
+
+    /// Returns the replacement strategy used by [`Population::envolve`].
+    #[inline]
+    #[must_use]
+    pub fn replacement(&self) -> Replacement {
+        self.replacement
     }
 
     /// Returns the size of the population.
@@ -87,46 +191,175 @@ impl Population {
         &self.chromosomes
     }
 
-    /// Evolves the population by applying selection and crossover operations.
+    /// Evolves the population by applying selection, crossover and mutation operations.
     ///
     /// The method iteratively selects parent chromosomes using the provided selection strategy,
-    /// applies the crossover operator to generate offspring, and replaces the population with
-    /// the newly generated chromosomes.
+    /// applies the crossover operator to generate offspring, mutates them, and inserts them
+    /// into the population according to its [`Replacement`] strategy.
     ///
     /// # Parameters
     /// - `selector: &S`: A reference to a selection strategy that implements the `Selection` trait.
     ///   The selector is used to choose parent chromosomes from the current population.
     /// - `crossover: &C`: A reference to a crossover strategy that implements the `Crossover` trait.
     ///   The crossover operator generates offspring chromosomes from selected parent chromosomes.
-    /// - `graph: &UnGraph<u32, ()>`: A reference to the underlying graph structure, used to validate
+    /// - `mutation: &M`: A reference to a mutation strategy that implements the `Mutation` trait.
+    ///   It is applied to every offspring, according to its own mutation rate.
+    /// - `graph: &CsrGraph`: A reference to the underlying graph structure, used to validate
     ///   or influence the crossover operation.
+    /// - `rng: &mut dyn RngCore`: The random number generator shared by the three operators, so a
+    ///   seeded generator makes the whole generation reproducible.
     ///
     /// # Behavior
-    /// 1. A new vector of chromosomes is pre-allocated with the same size as the current population.
-    /// 2. While the number of new chromosomes is less than the population size:
-    ///    - Two parent chromosomes are selected using the provided `selector`.
-    ///    - The `crossover` operator is applied to generate two offspring chromosomes.
-    ///    - The offspring chromosomes are added to the new population.
-    /// 3. Once the new population is complete, the chromosomes are added back to the population.
+    /// - [`Replacement::Generational`]: the `elitism` best chromosomes are copied unchanged, and
+    ///   offspring fill the rest of the new generation, which replaces the current one.
+    /// - [`Replacement::SteadyState`]: `offspring` children are generated, and each replaces the
+    ///   worst chromosome of the population if it is not worse than it.
+    ///
+    /// # Returns
+    /// - How many children were generated and how many of them were repaired.
     #[inline]
-    pub fn envolve<S: Selection, C: Crossover>(
+    pub fn envolve<S: Selection + ?Sized, C: Crossover + ?Sized, M: Mutation + ?Sized>(
         &mut self,
         selector: &S,
         crossover: &C,
-        graph: &UndirectedGraph<usize>,
-    ) {
-        let mut new_chromosomes: Vec<Chromosome> = Vec::with_capacity(self.size);
+        mutation: &M,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
+    ) -> GenerationStats {
+        match self.replacement {
+            Replacement::Generational { elitism } => {
+                let mut new_chromosomes: Vec<Chromosome> = Vec::with_capacity(self.size + 1);
+                new_chromosomes.extend(self.ranked().take(elitism).cloned());
+
+                let offspring = self.offspring(
+                    self.size - elitism,
+                    selector,
+                    crossover,
+                    mutation,
+                    graph,
+                    rng,
+                );
+                let stats = GenerationStats::of(&offspring);
+                new_chromosomes.extend(offspring);
+
+                self.chromosomes = new_chromosomes;
+                stats
+            }
+            Replacement::SteadyState { offspring } => {
+                let offspring =
+                    self.offspring(offspring, selector, crossover, mutation, graph, rng);
+                let stats = GenerationStats::of(&offspring);
 
-        while new_chromosomes.len() < self.size() {
-            let parent1 = selector.select(self);
-            let parent2 = selector.select(self);
+                for child in offspring {
+                    let worst = self
+                        .chromosomes
+                        .iter()
+                        .enumerate()
+                        .max_by_key(|(_, chromosome)| chromosome.fitness())
+                        .map(|(idx, _)| idx);
+
+                    if let Some(worst) = worst {
+                        if child.fitness() <= self.chromosomes[worst].fitness() {
+                            self.chromosomes[worst] = child;
+                        }
+                    }
+                }
+                stats
+            }
+        }
+    }
+
+    /// Generates `count` mutated children from parents chosen by `selector`.
+    fn offspring<S: Selection + ?Sized, C: Crossover + ?Sized, M: Mutation + ?Sized>(
+        &self,
+        count: usize,
+        selector: &S,
+        crossover: &C,
+        mutation: &M,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
+    ) -> Vec<Chromosome> {
+        let mut children = Vec::with_capacity(count + 1);
+
+        // Os pais da geração são escolhidos de uma vez, em pares consecutivos.
+        let parents = selector.select_many(self, count + count % 2, rng);
+        for pair in parents.chunks_exact(2) {
+            let (mut child1, mut child2) = crossover.crossover(pair[0], pair[1], graph, rng);
+            mutation.mutate(&mut child1, graph, rng);
+            mutation.mutate(&mut child2, graph, rng);
+            children.push(child1);
+            children.push(child2);
+        }
+
+        children.truncate(count);
+        children
+    }
+
+    /// Iterates over the chromosomes from best to worst fitness.
+    fn ranked(&self) -> impl Iterator<Item = &Chromosome> {
+        let mut ranked: Vec<&Chromosome> = self.chromosomes.iter().collect();
+        ranked.sort_by_key(|chromosome| chromosome.fitness());
+        ranked.into_iter()
+    }
+
+    /// Summarizes the fitness values of the population.
+    ///
+    /// # Returns
+    /// - The best, worst, mean and median fitness, or all zeros for an empty population.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn fitness_stats(&self) -> FitnessStats {
+        let mut fitness: Vec<usize> = self.chromosomes.iter().map(Chromosome::fitness).collect();
+        fitness.sort_unstable();
+
+        let (Some(&best), Some(&worst)) = (fitness.first(), fitness.last()) else {
+            return FitnessStats::default();
+        };
+        let len = fitness.len();
+        // Para tamanho ímpar, os dois índices coincidem.
+        let median = (fitness[(len - 1) / 2] + fitness[len / 2]) as f64 / 2.0;
+
+        FitnessStats {
+            best,
+            worst,
+            mean: fitness.iter().sum::<usize>() as f64 / len as f64,
+            median,
+        }
+    }
+
+    /// Measures how different the chromosomes of the population still are.
+    ///
+    /// For each vertex, counts the chromosomes whose label differs from the most common label
+    /// of that vertex. The result is the mean of these counts over all vertices, divided by
+    /// the number of chromosomes. This equals the mean Hamming distance to the consensus
+    /// labeling, divided by the number of vertices. It costs `O(size · n)`.
+    ///
+    /// # Returns
+    /// - A value in `[0.0, 1.0)`. It is `0.0` when every chromosome is identical, or when the
+    ///   population or the graph is empty.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn diversity(&self) -> f64 {
+        let Some(order) = self.chromosomes.first().map(|c| c.genes().len()) else {
+            return 0.0;
+        };
+        if order == 0 {
+            return 0.0;
+        }
 
-            let (child1, child2) = crossover.crossover(parent1, parent2, graph);
-            new_chromosomes.push(child1);
-            new_chromosomes.push(child2);
+        let mut counts = vec![[0usize; 3]; order];
+        for chromosome in &self.chromosomes {
+            for (vertex, &label) in chromosome.genes().iter().enumerate() {
+                counts[vertex][usize::from(label.min(2))] += 1;
+            }
         }
 
-        self.chromosomes = new_chromosomes;
+        let disagreements: usize = counts
+            .iter()
+            .map(|labels| self.chromosomes.len() - labels.iter().max().unwrap_or(&0))
+            .sum();
+
+        disagreements as f64 / (order * self.chromosomes.len()) as f64
     }
 
     /// Returns a reference to the chromosome with the best fitness (lowest value).
@@ -136,4 +369,215 @@ impl Population {
             .iter()
             .min_by_key(|chromosome| chromosome.fitness())
     }
+
+    /// Writes the population in a line-based text format read back by [`Population::read`]:
+    /// a `replacement` line, then one `chromosome` line per chromosome with its labels as
+    /// written by the `Display` of [`Chromosome`].
+    ///
+    /// # Parameters
+    /// - `out: &mut W`: The destination.
+    ///
+    /// # Errors
+    /// - The errors of `out`.
+    pub fn write<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        match self.replacement {
+            Replacement::Generational { elitism } => {
+                writeln!(out, "replacement generational {elitism}")?;
+            }
+            Replacement::SteadyState { offspring } => {
+                writeln!(out, "replacement steady-state {offspring}")?;
+            }
+        }
+        for chromosome in &self.chromosomes {
+            writeln!(out, "chromosome {chromosome}")?;
+        }
+        Ok(())
+    }
+
+    /// Reads a population written by [`Population::write`]. Empty lines and lines starting
+    /// with `#` are skipped; without a `replacement` line, the default one is used.
+    ///
+    /// # Parameters
+    /// - `input: R`: The source.
+    ///
+    /// # Errors
+    /// - An error of kind [`io::ErrorKind::InvalidData`] when a line is malformed, there is no
+    ///   chromosome or the chromosomes label different numbers of vertices.
+    /// - Other I/O errors of `input`, unchanged.
+    pub fn read<R: BufRead>(input: R) -> io::Result<Self> {
+        let mut chromosomes: Vec<Chromosome> = Vec::new();
+        let mut replacement = Replacement::default();
+
+        for (idx, line) in input.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            let invalid = |message: String| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: {message}", idx + 1),
+                )
+            };
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            match line.split_whitespace().collect::<Vec<_>>()[..] {
+                ["replacement", kind, count] => {
+                    let count = count
+                        .parse()
+                        .map_err(|_| invalid(format!("invalid count '{count}'")))?;
+                    replacement = match kind {
+                        "generational" => Replacement::Generational { elitism: count },
+                        "steady-state" => Replacement::SteadyState { offspring: count },
+                        _ => return Err(invalid(format!("unknown replacement '{kind}'"))),
+                    };
+                }
+                ["chromosome", genes] => {
+                    let chromosome: Chromosome =
+                        genes.parse().map_err(|e| invalid(format!("{e}")))?;
+                    if let Some(first) = chromosomes.first() {
+                        if first.genes().len() != chromosome.genes().len() {
+                            return Err(invalid(format!(
+                                "chromosome of length {}, expected {}",
+                                chromosome.genes().len(),
+                                first.genes().len()
+                            )));
+                        }
+                    }
+                    chromosomes.push(chromosome);
+                }
+                _ => return Err(invalid(format!("unexpected line '{line}'"))),
+            }
+        }
+
+        if chromosomes.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the population has no chromosome",
+            ));
+        }
+
+        let size = chromosomes.len();
+        Ok(Self {
+            chromosomes,
+            size,
+            replacement: Replacement::default(),
+        }
+        .with_replacement(replacement))
+    }
+}
+
+impl GenerationStats {
+    fn of(offspring: &[Chromosome]) -> Self {
+        Self {
+            evaluations: offspring.len(),
+            repaired: offspring.iter().filter(|c| c.was_repaired()).count(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::prelude::*;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::{
+        csr::test_graphs::random_connected,
+        genetic::{h1, h2, h3, h4, h5, KTournament, RandomRelabel, Uniform},
+    };
+
+    fn population(size: usize, graph: &CsrGraph, rng: &mut impl RngCore) -> Population {
+        Population::new(size, &[h1, h2, h3, h4, h5], graph, rng)
+    }
+
+    #[test]
+    fn new_fills_the_population_with_the_last_heuristic() {
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        let graph = CsrGraph::new(&random_connected(15, 0.2, &mut rng));
+        let population = population(8, &graph, &mut rng);
+
+        assert_eq!(population.size(), 8);
+        assert_eq!(population.chromosomes().len(), 8);
+        // h5 rotula todos os vértices com 1.
+        for chromosome in &population.chromosomes()[4..] {
+            assert_eq!(chromosome.genes(), [1; 15]);
+        }
+        assert!(population
+            .chromosomes()
+            .iter()
+            .all(|chromosome| !chromosome.was_repaired()));
+    }
+
+    #[test]
+    fn elitism_keeps_the_best_chromosome() {
+        let mut rng = ChaCha8Rng::seed_from_u64(4);
+        let graph = CsrGraph::new(&random_connected(25, 0.2, &mut rng));
+        let mut population = population(10, &graph, &mut rng)
+            .with_replacement(Replacement::Generational { elitism: 2 });
+        let (selection, crossover, mutation) = (
+            KTournament::new(2),
+            Uniform::new(1.0),
+            RandomRelabel::new(1.0),
+        );
+
+        for _ in 0..30 {
+            let best = population.best_chromosome().unwrap().clone();
+            let stats = population.envolve(&selection, &crossover, &mutation, &graph, &mut rng);
+
+            assert_eq!(stats.evaluations, 8);
+            assert_eq!(population.chromosomes().len(), 10);
+            assert!(population
+                .chromosomes()
+                .iter()
+                .any(|chromosome| chromosome.genes() == best.genes()));
+            assert!(population.best_chromosome().unwrap().fitness() <= best.fitness());
+        }
+    }
+
+    #[test]
+    fn generational_replacement_without_elitism_replaces_everything() {
+        let mut rng = ChaCha8Rng::seed_from_u64(4);
+        let graph = CsrGraph::new(&random_connected(25, 0.2, &mut rng));
+        let mut population = population(9, &graph, &mut rng);
+
+        let stats = population.envolve(
+            &KTournament::new(2),
+            &Uniform::new(1.0),
+            &RandomRelabel::new(0.5),
+            &graph,
+            &mut rng,
+        );
+        assert_eq!(stats.evaluations, 9);
+        assert_eq!(population.chromosomes().len(), 9);
+    }
+
+    #[test]
+    fn steady_state_replaces_only_the_worst() {
+        let mut rng = ChaCha8Rng::seed_from_u64(6);
+        let graph = CsrGraph::new(&random_connected(25, 0.2, &mut rng));
+        let mut population = population(10, &graph, &mut rng)
+            .with_replacement(Replacement::SteadyState { offspring: 1 });
+        let (selection, crossover, mutation) = (
+            KTournament::new(3),
+            Uniform::new(1.0),
+            RandomRelabel::new(1.0),
+        );
+
+        for _ in 0..30 {
+            let before = population.chromosomes().to_vec();
+            let worst = before.iter().map(Chromosome::fitness).max().unwrap();
+            let stats = population.envolve(&selection, &crossover, &mutation, &graph, &mut rng);
+            assert_eq!(stats.evaluations, 1);
+
+            let changed: Vec<usize> = (0..before.len())
+                .filter(|&i| before[i].genes() != population.chromosomes()[i].genes())
+                .collect();
+            assert!(changed.len() <= 1, "{changed:?}");
+            for i in changed {
+                assert_eq!(before[i].fitness(), worst);
+                assert!(population.chromosomes()[i].fitness() <= worst);
+            }
+        }
+    }
 }
@@ -9,16 +9,45 @@ pub trait Selection {
     /// # Arguments
     ///
     /// * `population` - A reference to the population from which to select.
+    /// * `rng` - The random number generator driving the selection.
     ///
     /// # Returns
     ///
     /// A reference to the selected chromosome.
-    fn select<'a>(&self, population: &'a Population) -> &'a Chromosome;
+    fn select<'a>(&self, population: &'a Population, rng: &mut dyn RngCore) -> &'a Chromosome;
+
+    /// Selects `count` chromosomes from the population, for example the parents of a generation.
+    ///
+    /// The default implementation calls [`Selection::select`] `count` times. Schemes that
+    /// choose all parents at once, such as [`StochasticUniversal`], override it.
+    ///
+    /// # Arguments
+    ///
+    /// * `population` - A reference to the population from which to select.
+    /// * `count` - The number of chromosomes to select.
+    /// * `rng` - The random number generator driving the selection.
+    ///
+    /// # Returns
+    ///
+    /// The selected chromosomes, in the order they should be paired.
+    fn select_many<'a>(
+        &self,
+        population: &'a Population,
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<&'a Chromosome> {
+        (0..count).map(|_| self.select(population, rng)).collect()
+    }
 }
 
 /// K-Tournament selection implementation.
+///
+/// Contestants are drawn with replacement by default, so the same chromosome may appear more
+/// than once in a tournament; [`KTournament::without_replacement`] makes them distinct.
+#[derive(Clone, Copy, Debug)]
 pub struct KTournament {
     k: usize,
+    distinct: bool,
 }
 
 impl KTournament {
@@ -34,35 +63,396 @@ impl KTournament {
     #[inline]
     #[must_use]
     pub fn new(k: usize) -> Self {
-        Self { k }
+        Self { k, distinct: false }
+    }
+
+    /// Enables or disables drawing the contestants without replacement.
+    ///
+    /// # Arguments
+    ///
+    /// * `distinct` - Whether every contestant of a tournament is a different chromosome. When
+    ///   `k` exceeds the population size, the whole population takes part.
+    ///
+    /// # Returns
+    ///
+    /// The operator with the new setting.
+    #[inline]
+    #[must_use]
+    pub fn without_replacement(mut self, distinct: bool) -> Self {
+        self.distinct = distinct;
+        self
     }
 }
 
 impl Selection for KTournament {
     /// Selects a chromosome from the population using K-Tournament selection.
     ///
+    /// The fitness of each chromosome is cached, so a tournament costs `O(k)`.
+    ///
     /// # Arguments
     ///
     /// * `population` - A reference to the population from which to select.
+    /// * `rng` - The random number generator used to draw the contestants.
     ///
     /// # Returns
     ///
     /// A reference to the selected chromosome.
-    fn select<'a>(&self, population: &'a Population) -> &'a Chromosome {
-        let mut rng = thread_rng();
-        let pop_size = population.size();
+    fn select<'a>(&self, population: &'a Population, rng: &mut dyn RngCore) -> &'a Chromosome {
+        let chromosomes = population.chromosomes();
+        let fitness = |idx: &usize| chromosomes[*idx].fitness();
 
-        let mut indices = Vec::with_capacity(self.k);
-        for _ in 0..self.k {
-            indices.push(rng.gen_range(0..pop_size));
+        let best_idx = if self.distinct {
+            let amount = self.k.min(chromosomes.len());
+            rand::seq::index::sample(rng, chromosomes.len(), amount)
+                .into_iter()
+                .min_by_key(fitness)
+        } else {
+            (0..self.k)
+                .map(|_| rng.gen_range(0..chromosomes.len()))
+                .min_by_key(fitness)
+        };
+
+        &chromosomes[best_idx.unwrap_or(0)]
+    }
+}
+
+/// Selection probability of each chromosome proportional to the inverse of its weight, since
+/// the algorithm minimizes. A zero weight counts as `1`.
+#[allow(clippy::cast_precision_loss)]
+fn inverse_weights(population: &Population) -> Vec<f64> {
+    population
+        .chromosomes()
+        .iter()
+        .map(|chromosome| 1.0 / chromosome.fitness().max(1) as f64)
+        .collect()
+}
+
+/// Returns the cumulative sums of `weights`.
+fn cumulative(weights: &[f64]) -> Vec<f64> {
+    weights
+        .iter()
+        .scan(0.0, |total, &weight| {
+            *total += weight;
+            Some(*total)
+        })
+        .collect()
+}
+
+/// Returns the index whose slice of the wheel `cumulative` contains `point`.
+fn spin(cumulative: &[f64], point: f64) -> usize {
+    cumulative
+        .partition_point(|&total| total <= point)
+        .min(cumulative.len().saturating_sub(1))
+}
+
+/// Draws one index of the wheel `cumulative` at random.
+fn spin_random(cumulative: &[f64], rng: &mut dyn RngCore) -> usize {
+    let total = cumulative.last().copied().unwrap_or(0.0);
+    if total > 0.0 {
+        spin(cumulative, rng.gen_range(0.0..total))
+    } else {
+        0
+    }
+}
+
+/// Fitness-proportional (roulette wheel) selection.
+///
+/// Each chromosome is chosen with probability proportional to `1 / weight`, so lighter
+/// labelings are preferred while heavier ones keep a chance to reproduce.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Roulette;
+
+impl Roulette {
+    /// Creates a new instance of `Roulette`.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Selection for Roulette {
+    fn select<'a>(&self, population: &'a Population, rng: &mut dyn RngCore) -> &'a Chromosome {
+        let wheel = cumulative(&inverse_weights(population));
+        &population.chromosomes()[spin_random(&wheel, rng)]
+    }
+
+    fn select_many<'a>(
+        &self,
+        population: &'a Population,
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<&'a Chromosome> {
+        // A roda é montada uma única vez para todos os sorteios.
+        let wheel = cumulative(&inverse_weights(population));
+        (0..count)
+            .map(|_| &population.chromosomes()[spin_random(&wheel, rng)])
+            .collect()
+    }
+}
+
+/// Linear rank selection.
+///
+/// Chromosomes are ranked from worst to best weight and the one of rank `r` (from `0` to
+/// `n - 1`) is chosen with probability `(2 - sp + 2 (sp - 1) r / (n - 1)) / n`, where `sp` is
+/// the selection pressure: the expected number of copies of the best chromosome. Unlike the
+/// roulette, the pressure does not depend on how far apart the weights are.
+#[derive(Clone, Copy, Debug)]
+pub struct LinearRank {
+    pressure: f64,
+}
+
+impl LinearRank {
+    /// Creates a new instance of `LinearRank`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pressure` - The selection pressure, in `[1.0, 2.0]`. `1.0` selects uniformly and
+    ///   `2.0` never selects the worst chromosome.
+    ///
+    /// # Panics
+    ///
+    /// If `pressure` is outside the range `[1.0, 2.0]`.
+    #[inline]
+    #[must_use]
+    pub fn new(pressure: f64) -> Self {
+        assert!(
+            (1.0..=2.0).contains(&pressure),
+            "Selection pressure must be between 1 and 2"
+        );
+        Self { pressure }
+    }
+
+    /// Returns the indices of the population, from worst to best, and their wheel.
+    #[allow(clippy::cast_precision_loss)]
+    fn wheel(self, population: &Population) -> (Vec<usize>, Vec<f64>) {
+        let chromosomes = population.chromosomes();
+        let mut ranked: Vec<usize> = (0..chromosomes.len()).collect();
+        ranked.sort_by_key(|&idx| std::cmp::Reverse(chromosomes[idx].fitness()));
+
+        let last = ranked.len().saturating_sub(1).max(1) as f64;
+        let weights: Vec<f64> = (0..ranked.len())
+            .map(|rank| 2.0 - self.pressure + 2.0 * (self.pressure - 1.0) * rank as f64 / last)
+            .collect();
+
+        (ranked, cumulative(&weights))
+    }
+}
+
+impl Selection for LinearRank {
+    fn select<'a>(&self, population: &'a Population, rng: &mut dyn RngCore) -> &'a Chromosome {
+        let (ranked, wheel) = self.wheel(population);
+        &population.chromosomes()[ranked[spin_random(&wheel, rng)]]
+    }
+
+    fn select_many<'a>(
+        &self,
+        population: &'a Population,
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<&'a Chromosome> {
+        let (ranked, wheel) = self.wheel(population);
+        (0..count)
+            .map(|_| &population.chromosomes()[ranked[spin_random(&wheel, rng)]])
+            .collect()
+    }
+}
+
+/// Stochastic universal sampling (SUS).
+///
+/// Uses the same inverse-weight wheel as [`Roulette`], but [`Selection::select_many`] spins it
+/// once and picks `count` chromosomes at evenly spaced pointers, so each chromosome is selected
+/// a number of times within one of its expected value. The selected parents are shuffled
+/// before being paired. A single [`Selection::select`] is a plain roulette spin.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StochasticUniversal;
+
+impl StochasticUniversal {
+    /// Creates a new instance of `StochasticUniversal`.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Selection for StochasticUniversal {
+    fn select<'a>(&self, population: &'a Population, rng: &mut dyn RngCore) -> &'a Chromosome {
+        Roulette.select(population, rng)
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn select_many<'a>(
+        &self,
+        population: &'a Population,
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<&'a Chromosome> {
+        let wheel = cumulative(&inverse_weights(population));
+        let total = wheel.last().copied().unwrap_or(0.0);
+        if count == 0 || total <= 0.0 {
+            return (0..count).map(|_| self.select(population, rng)).collect();
+        }
+
+        let step = total / count as f64;
+        let start = rng.gen_range(0.0..step);
+        let mut selected: Vec<&Chromosome> = (0..count)
+            .map(|i| &population.chromosomes()[spin(&wheel, start + i as f64 * step)])
+            .collect();
+
+        // Os ponteiros percorrem a roda em ordem; embaralhar evita parear vizinhos da roda.
+        selected.shuffle(rng);
+        selected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+
+    /// Population of one chromosome per labeling, in the given order.
+    fn population(labelings: &[&str]) -> Population {
+        let lines: Vec<String> = labelings
+            .iter()
+            .map(|genes| format!("chromosome {genes}"))
+            .collect();
+        Population::read(lines.join("\n").as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn tournament_of_the_whole_population_picks_the_best() {
+        let population = population(&["2222", "2200", "1000", "1100", "2220"]);
+        let selection = KTournament::new(population.size()).without_replacement(true);
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+
+        for _ in 0..100 {
+            assert_eq!(selection.select(&population, &mut rng).fitness(), 1);
         }
+    }
+
+    #[test]
+    fn tournament_larger_than_the_population_is_clamped() {
+        let population = population(&["2200", "1000", "2222"]);
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+
+        let selection = KTournament::new(10).without_replacement(true);
+        let selected = selection.select_many(&population, 20, &mut rng);
+        assert_eq!(selected.len(), 20);
+        assert!(selected.iter().all(|chromosome| chromosome.fitness() == 1));
 
-        let best_idx = indices
+        let selection = KTournament::new(10);
+        assert_eq!(selection.select_many(&population, 20, &mut rng).len(), 20);
+    }
+
+    /// Times each chromosome of `population` is among `selected`, by position.
+    fn times_selected(population: &Population, selected: &[&Chromosome]) -> Vec<usize> {
+        population
+            .chromosomes()
             .iter()
-            .min_by_key(|&&idx| population.chromosomes()[idx].fitness())
-            .copied()
-            .unwrap_or(0);
+            .map(|chromosome| {
+                selected
+                    .iter()
+                    .filter(|&&picked| std::ptr::eq(picked, chromosome))
+                    .count()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn lighter_chromosomes_are_chosen_more_often() {
+        let population = population(&["2222", "1000", "2200", "1100"]);
+        let schemes: [(&str, Box<dyn Selection>); 3] = [
+            ("roulette", Box::new(Roulette::new())),
+            ("rank", Box::new(LinearRank::new(2.0))),
+            ("sus", Box::new(StochasticUniversal::new())),
+        ];
+
+        for (name, selection) in schemes {
+            let mut rng = ChaCha8Rng::seed_from_u64(11);
+            let selected = selection.select_many(&population, 1000, &mut rng);
+            let counts = times_selected(&population, &selected);
+            assert!(counts[1] > counts[3], "{name}: {counts:?}");
+            assert!(counts[3] > counts[2], "{name}: {counts:?}");
+            assert!(counts[2] > counts[0], "{name}: {counts:?}");
+
+            let single: Vec<&Chromosome> = (0..1000)
+                .map(|_| selection.select(&population, &mut rng))
+                .collect();
+            let counts = times_selected(&population, &single);
+            assert!(counts[1] > counts[0], "{name}: {counts:?}");
+        }
+    }
+
+    #[test]
+    fn equal_weights_are_chosen_uniformly() {
+        for labelings in [["1100"; 4], ["0000"; 4]] {
+            let population = population(&labelings);
+            let schemes: [(&str, Box<dyn Selection>); 4] = [
+                ("roulette", Box::new(Roulette::new())),
+                // Tied chromosomes are ranked in population order, so only a pressure of 1
+                // leaves them equally likely.
+                ("rank", Box::new(LinearRank::new(1.0))),
+                ("sus", Box::new(StochasticUniversal::new())),
+                ("tournament", Box::new(KTournament::new(2))),
+            ];
+
+            for (name, selection) in schemes {
+                let mut rng = ChaCha8Rng::seed_from_u64(3);
+                let selected = selection.select_many(&population, 400, &mut rng);
+                let counts = times_selected(&population, &selected);
+                assert!(
+                    counts.iter().all(|&count| (50..=150).contains(&count)),
+                    "{name}: {counts:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn a_single_chromosome_is_always_chosen() {
+        let population = population(&["0000"]);
+        let schemes: [Box<dyn Selection>; 4] = [
+            Box::new(Roulette::new()),
+            Box::new(LinearRank::new(2.0)),
+            Box::new(StochasticUniversal::new()),
+            Box::new(KTournament::new(3).without_replacement(true)),
+        ];
+
+        for selection in schemes {
+            let mut rng = ChaCha8Rng::seed_from_u64(3);
+            let selected = selection.select_many(&population, 5, &mut rng);
+            assert_eq!(times_selected(&population, &selected), [5]);
+        }
+    }
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn sus_picks_each_chromosome_within_one_of_its_share() {
+        // Inverse weights 1, 1/2, 1/4 and 1/4: halves, quarters and eighths of the wheel.
+        let population = population(&["1000", "1100", "2200", "1111"]);
+        let shares = [0.5, 0.25, 0.125, 0.125];
+        let selection = StochasticUniversal::new();
+
+        for (seed, count) in [(1, 10), (2, 7), (3, 1), (4, 23)] {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let selected = selection.select_many(&population, count, &mut rng);
+            assert_eq!(selected.len(), count);
+
+            for (picked, share) in times_selected(&population, &selected)
+                .into_iter()
+                .zip(shares)
+            {
+                let expected = share * count as f64;
+                assert!(
+                    (picked as f64 - expected).abs() < 1.0,
+                    "{count} picks: {picked}, expected {expected}"
+                );
+            }
+        }
 
-        &population.chromosomes()[best_idx]
+        let mut rng = ChaCha8Rng::seed_from_u64(5);
+        assert!(selection.select_many(&population, 0, &mut rng).is_empty());
     }
 }
@@ -0,0 +1,184 @@
+use std::time::Duration;
+
+use super::Population;
+
+/// State of a run after a generation, observed by the termination criteria.
+#[derive(Clone, Copy)]
+pub struct Progress<'a> {
+    /// Number of generations evolved so far.
+    pub generation: usize,
+    /// Number of consecutive generations without improving the best fitness.
+    pub stagnant_generations: usize,
+    /// Number of chromosomes evaluated so far, including the initial population.
+    pub evaluations: usize,
+    /// Time since the run started.
+    pub elapsed: Duration,
+    /// Best fitness found so far.
+    pub best_fitness: usize,
+    /// The current population.
+    pub population: &'a Population,
+}
+
+/// Trait defining when a run of the genetic algorithm stops.
+pub trait Termination {
+    /// Returns `true` if the run should stop, given its current progress.
+    ///
+    /// It is checked before every generation, so a criterion that already holds for the
+    /// initial population stops the run before any generation is evolved.
+    fn should_stop(&self, progress: &Progress) -> bool;
+}
+
+/// Stops after a fixed number of generations.
+#[derive(Clone, Copy, Debug)]
+pub struct MaxGenerations(pub usize);
+
+impl Termination for MaxGenerations {
+    fn should_stop(&self, progress: &Progress) -> bool {
+        progress.generation >= self.0
+    }
+}
+
+/// Stops after a number of consecutive generations without improvement.
+#[derive(Clone, Copy, Debug)]
+pub struct Stagnation(pub usize);
+
+impl Termination for Stagnation {
+    fn should_stop(&self, progress: &Progress) -> bool {
+        progress.stagnant_generations >= self.0
+    }
+}
+
+/// Stops once the run has lasted a wall-clock time limit.
+///
+/// The limit is checked between generations, so a run exceeds it by at most one generation.
+#[derive(Clone, Copy, Debug)]
+pub struct TimeLimit(pub Duration);
+
+impl Termination for TimeLimit {
+    fn should_stop(&self, progress: &Progress) -> bool {
+        progress.elapsed >= self.0
+    }
+}
+
+/// Stops once a number of chromosomes have been evaluated.
+#[derive(Clone, Copy, Debug)]
+pub struct EvaluationBudget(pub usize);
+
+impl Termination for EvaluationBudget {
+    fn should_stop(&self, progress: &Progress) -> bool {
+        progress.evaluations >= self.0
+    }
+}
+
+/// Stops once the best fitness reaches a target, such as a known optimum or lower bound.
+#[derive(Clone, Copy, Debug)]
+pub struct TargetFitness(pub usize);
+
+impl Termination for TargetFitness {
+    fn should_stop(&self, progress: &Progress) -> bool {
+        progress.best_fitness <= self.0
+    }
+}
+
+/// Stops once the population has converged, that is, its [`Population::diversity`] is at most
+/// a threshold.
+#[derive(Clone, Copy, Debug)]
+pub struct DiversityCollapse(pub f64);
+
+impl Termination for DiversityCollapse {
+    fn should_stop(&self, progress: &Progress) -> bool {
+        progress.population.diversity() <= self.0
+    }
+}
+
+/// Stops when any of its criteria holds (OR). With no criteria, it never stops.
+#[derive(Default)]
+pub struct AnyOf(pub Vec<Box<dyn Termination + Sync>>);
+
+impl Termination for AnyOf {
+    fn should_stop(&self, progress: &Progress) -> bool {
+        self.0
+            .iter()
+            .any(|criterion| criterion.should_stop(progress))
+    }
+}
+
+/// Stops when all of its criteria hold (AND). With no criteria, it stops immediately.
+#[derive(Default)]
+pub struct AllOf(pub Vec<Box<dyn Termination + Sync>>);
+
+impl Termination for AllOf {
+    fn should_stop(&self, progress: &Progress) -> bool {
+        self.0
+            .iter()
+            .all(|criterion| criterion.should_stop(progress))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Criterion with a fixed answer.
+    struct Holds(bool);
+
+    impl Termination for Holds {
+        fn should_stop(&self, _: &Progress) -> bool {
+            self.0
+        }
+    }
+
+    fn criteria(answers: &[bool]) -> Vec<Box<dyn Termination + Sync>> {
+        answers
+            .iter()
+            .map(|&answer| Box::new(Holds(answer)) as Box<dyn Termination + Sync>)
+            .collect()
+    }
+
+    fn stops(rule: &dyn Termination) -> bool {
+        let population =
+            Population::read("replacement generational 0\nchromosome 11\n".as_bytes()).unwrap();
+        rule.should_stop(&Progress {
+            generation: 0,
+            stagnant_generations: 0,
+            evaluations: 1,
+            elapsed: Duration::ZERO,
+            best_fitness: 2,
+            population: &population,
+        })
+    }
+
+    #[test]
+    fn any_of_stops_when_one_criterion_holds() {
+        assert!(!stops(&AnyOf(criteria(&[]))));
+        assert!(!stops(&AnyOf(criteria(&[false]))));
+        assert!(!stops(&AnyOf(criteria(&[false, false]))));
+        assert!(stops(&AnyOf(criteria(&[true]))));
+        assert!(stops(&AnyOf(criteria(&[false, true]))));
+        assert!(stops(&AnyOf(criteria(&[true, true]))));
+    }
+
+    #[test]
+    fn all_of_stops_when_every_criterion_holds() {
+        assert!(stops(&AllOf(criteria(&[]))));
+        assert!(!stops(&AllOf(criteria(&[false]))));
+        assert!(!stops(&AllOf(criteria(&[true, false]))));
+        assert!(stops(&AllOf(criteria(&[true]))));
+        assert!(stops(&AllOf(criteria(&[true, true]))));
+    }
+
+    #[test]
+    fn combinations_nest() {
+        let nested = AllOf(vec![
+            Box::new(AnyOf(criteria(&[false, true]))),
+            Box::new(Holds(true)),
+        ]);
+        assert!(stops(&nested));
+
+        let nested = AnyOf(vec![
+            Box::new(AllOf(criteria(&[true, false]))),
+            Box::new(Holds(false)),
+        ]);
+        assert!(!stops(&nested));
+    }
+}
@@ -8,9 +8,21 @@
 //!
 //! ## Modules
 //! - `chromosome`: Defines the structure and operations for chromosomes.
+//! - `csr`: Immutable adjacency shared by the chromosomes and operators.
+//! - `exact`: Exact solver used to certify optima on small graphs.
+//! - `feasibility`: Checks that a graph admits a total Roman dominating function.
 
 /// Implementation of genetic operators
 pub mod genetic;
 
+/// Compressed sparse row adjacency
+pub mod csr;
+
+/// Exact solver based on integer programming
+pub mod exact;
+
+/// Feasibility analysis run before solving
+pub mod feasibility;
+
 /// Graph utils
 pub mod utils;
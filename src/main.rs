@@ -1,20 +1,49 @@
+mod batch;
+mod cli;
+mod config;
+mod report;
+
 use std::{
+    cell::Cell,
+    collections::HashMap,
     env,
-    fs::OpenOptions,
-    io::{self, Write},
-    process::exit,
+    fs::{self, OpenOptions},
+    io::{self, Read, Seek, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
     sync::Mutex,
-    time::Instant,
+    time::{Duration, Instant},
 };
 
 use cl_total_rdga::{
-    genetic::{h1, h2, h3, h4, h5, Heuristic, KTournament, Population, SinglePoint},
-    utils::build_graph,
+    csr::CsrGraph,
+    exact::ExactSolver,
+    feasibility::{remove_isolated, Feasibility},
+    genetic::{
+        h1, h2, h3, h4, h5, AllOf, AnyOf, Checkpoint, Chromosome, Crossover, DemoteTwo,
+        DiversityCollapse, EvaluationBudget, FitnessStats, Heuristic, KTournament, LinearRank,
+        MaxGenerations, Mutation, Neighborhood, Population, Progress, RandomRelabel, Replacement,
+        Roulette, Selection, SinglePoint, Stagnation, StochasticUniversal, SwapLabels,
+        TargetFitness, Termination, TimeLimit, TwoPoint, Uniform,
+    },
+    utils::{self, VertexMap},
+};
+use cli::{
+    BatchArgs, BenchArgs, Command, ExactArgs, InfoArgs, ReportArgs, SolveArgs, ValidateArgs,
+};
+use config::{
+    AlgorithmParams, CrossoverKind, ExperimentConfig, HeuristicKind, MutationKind, SelectionKind,
+    StopRule,
 };
 use env_logger::{Builder, Target};
-use kambo_graph::Graph;
+use kambo_graph::{graphs::simple::UndirectedGraph, Graph};
 use log::{debug, error, info, LevelFilter};
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rayon::{
+    iter::{IntoParallelRefIterator, ParallelIterator},
+    ThreadPoolBuilder,
+};
 
 #[derive(Debug)]
 struct TrialResult {
@@ -23,15 +52,24 @@ struct TrialResult {
     edge_count: usize,
     fitness: usize,
     elapsed_micros: u128,
+    /// Base seed of the run.
+    seed: u64,
+    /// Number of the trial, from `1`, which picks its generator (see [`trial_rng`]).
+    trial: usize,
+    best: Chromosome,
+    /// One row per generation, empty unless a trace was requested.
+    trace: Vec<TraceRow>,
 }
 
+/// State of a trial after a generation (generation `0` is the initial population).
 #[derive(Debug)]
-struct AlgorithmParams {
-    max_stagnant: usize,
-    generations: usize,
-    tournament_size: usize,
-    crossover_rate: f64,
-    pop_size: usize,
+struct TraceRow {
+    generation: usize,
+    evaluations: usize,
+    fitness: FitnessStats,
+    diversity: f64,
+    repaired: usize,
+    elapsed_micros: u128,
 }
 
 fn setup_logger() -> Result<(), io::Error> {
@@ -57,95 +95,261 @@ fn setup_logger() -> Result<(), io::Error> {
     Ok(())
 }
 
-fn parse_args() -> Result<(String, usize, String, AlgorithmParams), String> {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 4 {
-        return Err(format!(
-            "Usage: {} <file_path> <trials> <output_file> [max_stagnant] [generations] [tournament_size] [crossover_prob] [pop_size]",
-            args[0]
-        ));
-    }
+const RESULTS_HEADER: &str =
+    "graph_name,graph_order,graph_size,fitness_value,elapsed_time(microsecond),seed,trial,\
+     dropped_vertices";
 
-    let file_path = args[1].clone();
-    let trials = args[2].parse().map_err(|_| "Invalid trials parameter")?;
-    let output_file = args[3].clone();
+/// Destination of the result rows. Rows are written one trial at a time, as each trial
+/// finishes, and a file is synced after every row, so an interrupted run keeps every finished
+/// trial.
+enum ResultOutput {
+    Stdout,
+    File(fs::File),
+}
 
-    let params = AlgorithmParams {
-        max_stagnant: args.get(4).and_then(|s| s.parse().ok()).unwrap_or(100),
-        generations: args.get(5).and_then(|s| s.parse().ok()).unwrap_or(1000),
-        tournament_size: args.get(6).and_then(|s| s.parse().ok()).unwrap_or(5),
-        crossover_rate: args.get(7).and_then(|s| s.parse().ok()).unwrap_or(0.9),
-        pop_size: args.get(8).and_then(|s| s.parse().ok()).unwrap_or(50),
-    };
+impl ResultOutput {
+    /// Opens `output` for appending (stdout when `None`). A new file, like stdout, starts with
+    /// the resolved config as `#` comment lines and then the header (see [`preamble`]), so
+    /// each row can be reproduced with the seed in the row. An existing file must have the
+    /// same columns and config (see [`check_config`]), and a partially written last row, left
+    /// by an interrupted run, is dropped first.
+    fn open(output: Option<&str>, config: &ExperimentConfig) -> io::Result<Self> {
+        let preamble = preamble(config);
+        let Some(path) = output else {
+            let mut out = Self::Stdout;
+            out.write_synced(&preamble)?;
+            return Ok(out);
+        };
 
-    info!(
-        "Parsed arguments - File: {}, Trials: {}, Output: {}",
-        file_path, trials, output_file
-    );
-    debug!("Algorithm parameters: {:?}", params);
-
-    Ok((file_path, trials, output_file, params))
-}
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| {
+                error!("Failed to open output file: {}", e);
+                e
+            })?;
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
 
-fn write_results_to_csv(results: &[TrialResult], output_file: &str) -> io::Result<()> {
-    let mut file = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(output_file)
-        .map_err(|e| {
-            error!("Failed to open output file: {}", e);
-            e
-        })?;
+        let no_header = content
+            .split_inclusive('\n')
+            .all(|line| line.starts_with('#') || RESULTS_HEADER.starts_with(line));
+        if no_header {
+            // Um arquivo novo, ou interrompido antes do fim do cabeçalho, começa de novo.
+            debug!("Creating new CSV file with header");
+            file.set_len(0)?;
+            let mut out = Self::File(file);
+            out.write_synced(&preamble)?;
+            return Ok(out);
+        }
 
-    if file.metadata()?.len() == 0 {
-        debug!("Creating new CSV file with header");
-        writeln!(
-            file,
-            "graph_name,graph_order,graph_size,fitness_value,elapsed_time(microsecond)"
-        )?;
+        check_header(path, &content)?;
+        check_config(path, &content, config)?;
+        file.rewind()?;
+        drop_partial_row(&mut file)?;
+        Ok(Self::File(file))
     }
 
-    for result in results {
+    /// Appends the row of `result`, solved on a graph without the `dropped` isolated vertices
+    /// of the file (see [`load_feasible_graph`]).
+    fn write(&mut self, result: &TrialResult, dropped: usize) -> io::Result<()> {
         debug!("Writing result: {:?}", result);
-        writeln!(
-            file,
-            "{},{},{},{},{}",
+        let row = format!(
+            "{},{},{},{},{},{},{},{}",
             result.graph_name,
             result.node_count,
             result.edge_count,
             result.fitness,
-            result.elapsed_micros
-        )?;
+            result.elapsed_micros,
+            result.seed,
+            result.trial,
+            dropped
+        );
+        self.copy(&row)
+    }
+
+    /// Appends `row`, a row without its newline read from another results file.
+    fn copy(&mut self, row: &str) -> io::Result<()> {
+        self.write_synced(&format!("{row}\n"))
+            .map_err(|e| io::Error::new(e.kind(), format!("failed to write results: {e}")))
+    }
+
+    fn write_synced(&mut self, text: &str) -> io::Result<()> {
+        match self {
+            Self::Stdout => {
+                let mut out = io::stdout().lock();
+                out.write_all(text.as_bytes())?;
+                out.flush()
+            }
+            Self::File(file) => {
+                // Uma única escrita por linha, para que execuções paralelas não se intercalem.
+                file.write_all(text.as_bytes())?;
+                file.sync_data()
+            }
+        }
+    }
+}
+
+/// Returns the first lines of a results file: the resolved config, commented out with `#`,
+/// and [`RESULTS_HEADER`].
+fn preamble(config: &ExperimentConfig) -> String {
+    let mut text = String::new();
+    for line in config.to_toml().lines() {
+        text.push('#');
+        if !line.is_empty() {
+            text.push(' ');
+            text.push_str(line);
+        }
+        text.push('\n');
+    }
+    text + RESULTS_HEADER + "\n"
+}
+
+/// Returns the first complete line of a results file that is not a comment, its header.
+fn header_line(content: &str) -> Option<&str> {
+    content
+        .split_inclusive('\n')
+        .find(|line| !line.starts_with('#'))
+        .filter(|line| line.ends_with('\n'))
+        .map(str::trim_end)
+}
+
+/// Fails unless the results file `path`, whose text is `content`, has no header yet or has
+/// [`RESULTS_HEADER`], so rows are never appended under the columns of another format.
+fn check_header(path: &str, content: &str) -> io::Result<()> {
+    match header_line(content) {
+        Some(header) if header != RESULTS_HEADER => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "'{path}' has the columns '{header}' instead of '{RESULTS_HEADER}'; write to \
+                 another file"
+            ),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Fails unless the config commented out at the top of the results file `path` is `config`
+/// but for the seed, the number of trials and the single trial run, which each row records,
+/// since rows of another experiment would be mixed with its rows.
+fn check_config(path: &str, content: &str, config: &ExperimentConfig) -> io::Result<()> {
+    let saved: String = content
+        .lines()
+        .map_while(|line| line.strip_prefix('#'))
+        .map(|line| format!("{}\n", line.strip_prefix(' ').unwrap_or(line)))
+        .collect();
+    if saved.trim().is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("'{path}' does not record the config of its rows; write to another file"),
+        ));
+    }
+
+    let saved = ExperimentConfig::from_toml(&saved, ExperimentConfig::default())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("'{path}': {e}")))?;
+    let saved = ExperimentConfig {
+        trials: config.trials,
+        trial: config.trial,
+        seed: config.seed,
+        ..saved
+    };
+    if saved.to_toml() != config.to_toml() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("the rows in '{path}' come from another configuration; write to another file"),
+        ));
     }
 
     Ok(())
 }
 
-fn main() {
-    if let Err(e) = setup_logger() {
-        eprintln!("Failed to setup logger: {}", e);
-        exit(1);
+/// Truncates `file` after its last newline, removing a row cut short by an interruption.
+fn drop_partial_row(file: &mut fs::File) -> io::Result<()> {
+    let mut content = Vec::new();
+    file.read_to_end(&mut content)?;
+    if matches!(content.last(), None | Some(b'\n')) {
+        return Ok(());
     }
 
-    info!("Starting genetic algorithm execution");
+    let keep = content
+        .iter()
+        .rposition(|&byte| byte == b'\n')
+        .map_or(0, |idx| idx + 1);
+    info!(
+        "Dropping a partially written row of {} bytes",
+        content.len() - keep
+    );
+    file.set_len(keep as u64)?;
+    file.sync_data()
+}
 
-    let (file_path, trials, output_file, params) = match parse_args() {
-        Ok(args) => args,
-        Err(e) => {
-            error!("Error parsing arguments: {}", e);
-            eprintln!("Error: {}", e);
-            exit(1);
-        }
+/// Trials already in a results file, keyed by `(graph, trial, seed)`, with their rows.
+type DoneTrials = HashMap<(String, usize, u64), String>;
+
+/// Reads the trials already in the results file `path`, for `--resume`. A missing file has
+/// none, and a file with other columns is refused (see [`check_header`]). Rows that do not
+/// parse are not counted.
+fn completed_trials(path: &str) -> Result<DoneTrials, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(DoneTrials::new()),
+        Err(e) => return Err(format!("failed to read '{path}': {e}")),
     };
+    check_header(path, &content).map_err(|e| e.to_string())?;
 
-    let start_time = Instant::now();
+    let mut done = DoneTrials::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line == RESULTS_HEADER {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let parsed = fields
+            .get(6)
+            .and_then(|trial| trial.parse().ok())
+            .zip(fields.get(5).and_then(|seed| seed.parse().ok()));
+        if let Some((trial, seed)) = parsed {
+            done.insert((fields[0].to_string(), trial, seed), line.to_string());
+        }
+    }
+
+    Ok(done)
+}
+
+/// Returns the trials of `config`, numbered from `1`, of `graph_name` that are not yet in
+/// `done`.
+fn pending_trials(config: &ExperimentConfig, graph_name: &str, done: &DoneTrials) -> Vec<usize> {
+    let base_seed = config
+        .seed
+        .expect("the seed is resolved before running trials");
+    config
+        .trial_numbers()
+        .filter(|&trial| !done.contains_key(&(graph_name.to_string(), trial, base_seed)))
+        .collect()
+}
+
+/// Resolves the seed of a resumed run: an explicit `--seed` wins, then the base seed of the
+/// trials already done, so the missing trials get the generators the interrupted run would
+/// have used.
+fn resumed_seed(config: &ExperimentConfig, done: &DoneTrials) -> ExperimentConfig {
+    let mut config = config.clone();
+    if config.seed.is_none() {
+        config.seed = done.keys().map(|&(_, _, seed)| seed).min();
+    }
+    with_resolved_seed(&config)
+}
+
+fn load_graph(file_path: &str) -> Result<(UndirectedGraph<usize>, VertexMap), String> {
     info!("Building graph from file: {}", file_path);
-    let graph = build_graph(&file_path);
+    let (graph, vertices) =
+        utils::load_graph(file_path).map_err(|e| format!("failed to read graph: {e}"))?;
 
     if graph.order() == 0 {
         error!("Graph has no nodes");
-        eprintln!("The graph has no nodes. Exiting.");
-        exit(1);
+        return Err(format!("the graph in '{file_path}' has no nodes"));
     }
 
     info!(
@@ -154,100 +358,1272 @@ fn main() {
         graph.edge_count()
     );
 
+    Ok((graph, vertices))
+}
+
+/// Loads a graph to be solved, rejecting it when it admits no total Roman dominating function.
+///
+/// With `drop_isolated`, the isolated vertices are removed instead, and reported apart. Also
+/// returns how many vertices were removed, so the order of the file can be recovered.
+fn load_feasible_graph(
+    file_path: &str,
+    drop_isolated: bool,
+) -> Result<(UndirectedGraph<usize>, VertexMap, usize), String> {
+    let (graph, vertices) = load_graph(file_path)?;
+    let feasibility = Feasibility::analyze(&graph);
+    info!(
+        "Feasibility - Components: {}, Isolated vertices: {}",
+        feasibility.components().len(),
+        feasibility.isolated_vertices().len()
+    );
+
+    let Err(err) = feasibility.check() else {
+        return Ok((graph, vertices, 0));
+    };
+
+    let isolated: Vec<String> = feasibility
+        .isolated_vertices()
+        .iter()
+        .map(|&v| vertices.original(v).unwrap_or(v).to_string())
+        .collect();
+    let isolated = isolated.join(" ");
+
+    if !drop_isolated {
+        error!("{}: {}", err, isolated);
+        return Err(format!(
+            "{err} ({isolated}); use --drop-isolated to solve the rest of the graph"
+        ));
+    }
+
+    let (reduced, kept) = remove_isolated(&graph);
+    if reduced.order() == 0 {
+        return Err(format!("{err}, and no other vertex is left to solve"));
+    }
+
+    let mut reduced_vertices = VertexMap::new();
+    for &v in &kept {
+        reduced_vertices.insert(vertices.original(v).unwrap_or(v));
+    }
+
+    info!("Excluded isolated vertices: {}", isolated);
+    eprintln!(
+        "excluded isolated vertices ({isolated}), solving the remaining {} vertices",
+        reduced.order()
+    );
+
+    Ok((reduced, reduced_vertices, graph.order() - kept.len()))
+}
+
+/// Builds the stopping rule of a trial from the criteria that are set, combined as
+/// `params.stop_when` says. A generation or stagnation limit of `0` is not set.
+///
+/// With [`StopRule::All`], the generation and time limits stay hard caps: the run stops when
+/// either is reached, or when all the other criteria hold, so an unreachable target or
+/// diversity cannot keep it running forever.
+fn termination(params: &AlgorithmParams) -> Box<dyn Termination + Sync> {
+    let mut limits: Vec<Box<dyn Termination + Sync>> = Vec::new();
+    if params.generations > 0 {
+        limits.push(Box::new(MaxGenerations(params.generations)));
+    }
+    if let Some(secs) = params.time_limit {
+        limits.push(Box::new(TimeLimit(Duration::from_secs_f64(secs))));
+    }
+
+    let mut criteria: Vec<Box<dyn Termination + Sync>> = Vec::new();
+    if params.max_stagnant > 0 {
+        criteria.push(Box::new(Stagnation(params.max_stagnant)));
+    }
+    if let Some(budget) = params.max_evaluations {
+        criteria.push(Box::new(EvaluationBudget(budget)));
+    }
+    if let Some(target) = params.target_fitness {
+        criteria.push(Box::new(TargetFitness(target)));
+    }
+    if let Some(diversity) = params.min_diversity {
+        criteria.push(Box::new(DiversityCollapse(diversity)));
+    }
+
+    match params.stop_when {
+        StopRule::Any => {
+            limits.extend(criteria);
+            Box::new(AnyOf(limits))
+        }
+        // `AllOf` sem critérios pararia de imediato, então só entra quando há algum.
+        StopRule::All if criteria.is_empty() => Box::new(AnyOf(limits)),
+        StopRule::All => {
+            limits.push(Box::new(AllOf(criteria)));
+            Box::new(AnyOf(limits))
+        }
+    }
+}
+
+/// What [`run_trials`] does besides running the trials.
+#[derive(Default)]
+struct RunOptions<'a> {
+    /// Record a [`TraceRow`] per generation.
+    trace: bool,
+    /// Directory of the checkpoints and number of generations between two of them.
+    checkpoints: Option<(&'a str, usize)>,
+    /// Checkpoint that the trial with its number continues from, instead of starting over.
+    restore: Option<&'a Checkpoint>,
+}
+
+/// Returns the checkpoint file of a trial, `<dir>/<graph>-<seed>-<trial>.ckpt`.
+fn checkpoint_path(dir: &str, graph_name: &str, seed: u64, trial: usize) -> PathBuf {
+    let stem = Path::new(graph_name)
+        .file_stem()
+        .map_or_else(|| "graph".into(), |stem| stem.to_string_lossy());
+    Path::new(dir).join(format!("{stem}-{seed}-{trial}.ckpt"))
+}
+
+/// Returns the generator of trial `trial` of a run with base seed `seed`. Every trial draws
+/// from its own stream of the ChaCha generator keyed by the seed, so no two trials share
+/// numbers, not even trials of runs with consecutive seeds.
+fn trial_rng(seed: u64, trial: usize) -> ChaCha8Rng {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    rng.set_stream(trial as u64);
+    rng
+}
+
+/// Runs `trials` of the GA on `graph`, passing each result to `on_trial` as soon as the trial
+/// finishes.
+///
+/// A trial whose best labeling is not a valid total Roman dominating function fails with an
+/// error of kind [`io::ErrorKind::InvalidData`] before `on_trial` sees it, so its weight never
+/// reaches the results and its labeling never reaches a solution file.
+fn run_trials(
+    graph: &UndirectedGraph<usize>,
+    graph_name: &str,
+    config: &ExperimentConfig,
+    trials: &[usize],
+    options: &RunOptions,
+    on_trial: &(dyn Fn(&TrialResult) -> io::Result<()> + Sync),
+) -> io::Result<Vec<TrialResult>> {
+    let params = &config.params;
     let pop_size = if params.pop_size == 0 {
-        ((graph.order() as f64 / 1.5).ceil() as usize).max(1)
+        ((graph.order() as f64 / 1.5).ceil() as usize).max(params.elitism + 1)
     } else {
         params.pop_size
     };
 
     debug!("Using population size: {}", pop_size);
+    debug!("Experiment config: {:?}", config);
+
+    let heuristics: Vec<Heuristic> = config
+        .heuristics
+        .iter()
+        .map(|kind| match kind {
+            HeuristicKind::H1 => h1,
+            HeuristicKind::H2 => h2,
+            HeuristicKind::H3 => h3,
+            HeuristicKind::H4 => h4,
+            HeuristicKind::H5 => h5,
+        })
+        .collect();
+    let crossover: Box<dyn Crossover + Sync> = match config.crossover {
+        CrossoverKind::SinglePoint => {
+            Box::new(SinglePoint::new(params.crossover_rate).with_local_search(params.local_search))
+        }
+        CrossoverKind::TwoPoint => {
+            Box::new(TwoPoint::new(params.crossover_rate).with_local_search(params.local_search))
+        }
+        CrossoverKind::Uniform => {
+            Box::new(Uniform::new(params.crossover_rate).with_local_search(params.local_search))
+        }
+        CrossoverKind::Neighborhood => Box::new(
+            Neighborhood::new(params.crossover_rate).with_local_search(params.local_search),
+        ),
+    };
+    let mutation: Box<dyn Mutation + Sync> = match config.mutation {
+        MutationKind::RandomRelabel => Box::new(RandomRelabel::new(params.mutation_rate)),
+        MutationKind::SwapLabels => Box::new(SwapLabels::new(params.mutation_rate)),
+        MutationKind::DemoteTwo => Box::new(DemoteTwo::new(params.mutation_rate)),
+    };
+    let selector: Box<dyn Selection + Sync> = match config.selection {
+        SelectionKind::Tournament => Box::new(
+            KTournament::new(params.tournament_size)
+                .without_replacement(params.tournament_without_replacement),
+        ),
+        SelectionKind::Roulette => Box::new(Roulette::new()),
+        SelectionKind::Rank => Box::new(LinearRank::new(params.selection_pressure)),
+        SelectionKind::Sus => Box::new(StochasticUniversal::new()),
+    };
+    let termination = termination(params);
+    let replacement = Replacement::Generational {
+        elitism: params.elitism,
+    };
 
-    let heuristics: Vec<Heuristic> = vec![h1, h2, h3, h4, h5, h1];
-    let crossover = SinglePoint::new(params.crossover_rate);
-    let selector = KTournament::new(params.tournament_size);
-
-    info!("Starting {} trials", trials);
-    let results = Mutex::new(Vec::with_capacity(trials));
-
-    (0..trials).into_par_iter().for_each(|trial| {
-        info!("Starting trial {}", trial + 1);
-        let trial_start = Instant::now();
-
-        let mut population = Population::new(pop_size, &heuristics, &graph);
-        debug!("Initial population created for trial {}", trial + 1);
-
-        let mut best_solution = population
-            .best_chromosome()
-            .expect("Failed to retrieve the best individual")
-            .clone();
-
-        debug!("Initial best fitness: {}", best_solution.fitness());
-
-        let mut stagnant_generations = 0;
-        for generation in 0..params.generations {
-            population.envolve(&selector, &crossover, &graph);
-            let new_best_solution = population
-                .best_chromosome()
-                .expect("Failed to retrieve the best individual")
-                .clone();
-
-            if new_best_solution.fitness() < best_solution.fitness() {
-                debug!(
-                    "Trial {} - Generation {} - New best fitness: {} (improved from {})",
-                    trial + 1,
-                    generation + 1,
-                    new_best_solution.fitness(),
-                    best_solution.fitness()
+    // A adjacência compacta é construída uma vez e compartilhada por todas as execuções.
+    let csr = CsrGraph::new(graph);
+    let base_seed = config
+        .seed
+        .expect("the seed is resolved before running trials");
+    let settings = config.to_toml();
+
+    info!(
+        "Starting {} trials with base seed {}",
+        trials.len(),
+        base_seed
+    );
+
+    // Cada execução tem seu próprio gerador, então o resultado não depende do escalonamento do rayon.
+    trials
+        .par_iter()
+        .map(|&trial| {
+            let trial_start = Instant::now();
+            let restored = options
+                .restore
+                .filter(|checkpoint| checkpoint.trial == trial);
+            let Checkpoint {
+                mut rng,
+                mut population,
+                best: mut best_solution,
+                mut generation,
+                mut stagnant_generations,
+                mut evaluations,
+                elapsed: restored_elapsed,
+                ..
+            } = if let Some(checkpoint) = restored {
+                info!(
+                    "Restoring trial {} with seed {} at generation {}",
+                    trial, base_seed, checkpoint.generation
                 );
-                best_solution = new_best_solution;
-                stagnant_generations = 0;
+                checkpoint.clone()
             } else {
-                stagnant_generations += 1;
+                let mut rng = trial_rng(base_seed, trial);
+                info!("Starting trial {} with seed {}", trial, base_seed);
+
+                let mut population = Population::new(pop_size, &heuristics, &csr, &mut rng)
+                    .with_replacement(replacement);
+                if params.local_search {
+                    population.remove_redundancy(&csr);
+                }
+                debug!("Initial population created for trial {}", trial);
+
+                let best = population
+                    .best_chromosome()
+                    .expect("Failed to retrieve the best individual")
+                    .clone();
+                Checkpoint {
+                    graph: graph_name.to_string(),
+                    config: settings.clone(),
+                    trial,
+                    seed: base_seed,
+                    generation: 0,
+                    stagnant_generations: 0,
+                    evaluations: population.size(),
+                    elapsed: Duration::ZERO,
+                    best,
+                    rng,
+                    population,
+                }
+            };
+            // Uma execução restaurada continua contando o tempo gasto antes da interrupção. O
+            // tempo gasto no traço fica de fora, para não pesar no relógio da execução.
+            let tracing = Cell::new(Duration::ZERO);
+            let elapsed = || restored_elapsed + trial_start.elapsed() - tracing.get();
+
+            debug!("Initial best fitness: {}", best_solution.fitness());
+
+            let mut repaired = 0;
+            let mut rows = Vec::new();
+            let mut next_checkpoint =
+                generation + options.checkpoints.map_or(0, |(_, every)| every);
+            loop {
+                if options.trace {
+                    let elapsed_micros = elapsed().as_micros();
+                    let tracing_start = Instant::now();
+                    rows.push(TraceRow {
+                        generation,
+                        evaluations,
+                        fitness: population.fitness_stats(),
+                        diversity: population.diversity(),
+                        repaired,
+                        elapsed_micros,
+                    });
+                    tracing.set(tracing.get() + tracing_start.elapsed());
+                }
+
+                let progress = Progress {
+                    generation,
+                    stagnant_generations,
+                    evaluations,
+                    elapsed: elapsed(),
+                    best_fitness: best_solution.fitness(),
+                    population: &population,
+                };
+                if termination.should_stop(&progress) {
+                    info!(
+                        "Trial {} stopped after {} generations and {} evaluations",
+                        trial, generation, evaluations
+                    );
+                    break;
+                }
+
+                let stats = population.envolve(
+                    selector.as_ref(),
+                    crossover.as_ref(),
+                    mutation.as_ref(),
+                    &csr,
+                    &mut rng,
+                );
+                evaluations += stats.evaluations;
+                repaired = stats.repaired;
+                generation += 1;
+                let new_best_solution = population
+                    .best_chromosome()
+                    .expect("Failed to retrieve the best individual")
+                    .clone();
+
+                if new_best_solution.fitness() < best_solution.fitness() {
+                    debug!(
+                        "Trial {} - Generation {} - New best fitness: {} (improved from {})",
+                        trial,
+                        generation,
+                        new_best_solution.fitness(),
+                        best_solution.fitness()
+                    );
+                    best_solution = new_best_solution;
+                    stagnant_generations = 0;
+                } else {
+                    stagnant_generations += 1;
+                }
+
+                if let Some((dir, every)) = options.checkpoints {
+                    if generation >= next_checkpoint {
+                        let checkpoint = Checkpoint {
+                            graph: graph_name.to_string(),
+                            config: settings.clone(),
+                            trial,
+                            seed: base_seed,
+                            generation,
+                            stagnant_generations,
+                            evaluations,
+                            elapsed: elapsed(),
+                            best: best_solution.clone(),
+                            rng: rng.clone(),
+                            population: population.clone(),
+                        };
+                        let path = checkpoint_path(dir, graph_name, base_seed, trial);
+                        debug!("Saving checkpoint to {}", path.display());
+                        checkpoint.save(&path).map_err(|e| {
+                            io::Error::new(
+                                e.kind(),
+                                format!("failed to save checkpoint '{}': {e}", path.display()),
+                            )
+                        })?;
+                        next_checkpoint = generation + every;
+                    }
+                }
             }
 
-            if stagnant_generations >= params.max_stagnant {
-                info!(
-                    "Trial {} stopped at generation {} due to stagnation",
-                    trial + 1,
-                    generation + 1
+            let elapsed_time = elapsed();
+
+            // Um rótulo inválido nunca chega aos resultados nem ao arquivo de solução.
+            let violations = best_solution.violations(graph);
+            if !violations.is_empty() {
+                error!(
+                    "Trial {} ended with an invalid labeling: {:?}",
+                    trial, violations
                 );
-                break;
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "trial {trial} with seed {base_seed} ended with a labeling of weight {} \
+                         that breaks {} constraints; its result was not written",
+                        best_solution.fitness(),
+                        violations.len()
+                    ),
+                ));
+            }
+
+            info!(
+                "Trial {} completed - Final fitness: {}, Time: {:?}",
+                trial,
+                best_solution.fitness(),
+                elapsed_time
+            );
+
+            let result = TrialResult {
+                graph_name: graph_name.to_string(),
+                node_count: graph.order(),
+                edge_count: graph.edge_count(),
+                fitness: best_solution.fitness(),
+                elapsed_micros: elapsed_time.as_micros(),
+                seed: base_seed,
+                trial,
+                best: best_solution,
+                trace: rows,
+            };
+            // Cada execução é gravada assim que termina, para sobreviver a uma interrupção.
+            on_trial(&result)?;
+            if let Some((dir, _)) = options.checkpoints {
+                // Com a linha gravada, o ponto de restauração não serve mais.
+                let path = checkpoint_path(dir, graph_name, base_seed, trial);
+                match fs::remove_file(&path) {
+                    Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                        return Err(io::Error::new(
+                            e.kind(),
+                            format!("failed to remove checkpoint '{}': {e}", path.display()),
+                        ));
+                    }
+                    _ => debug!("Removed checkpoint {}", path.display()),
+                }
             }
+            Ok(result)
+        })
+        .collect()
+}
+
+/// Fixes the base seed of `config`, drawing one at random when none was given, so the
+/// echoed config reproduces the run.
+fn with_resolved_seed(config: &ExperimentConfig) -> ExperimentConfig {
+    let mut config = config.clone();
+    // Mantém a semente dentro do intervalo aceito pelo arquivo de configuração.
+    config
+        .seed
+        .get_or_insert_with(|| rand::random::<u64>() >> 1);
+    config
+}
+
+/// Writes the best labeling of `result` to `<dir>/<graph>-<seed>-<trial>.sol`, in the format
+/// read by `validate`. [`run_trials`] only passes on trials whose labeling is valid.
+///
+/// The file is synced before returning, since the row of the trial, written next, marks the
+/// trial as done for `--resume`.
+fn write_solution(
+    result: &TrialResult,
+    vertices: &VertexMap,
+    graph_path: &str,
+    dir: &str,
+) -> io::Result<()> {
+    let stem = Path::new(graph_path)
+        .file_stem()
+        .map_or_else(|| "graph".into(), |stem| stem.to_string_lossy());
+    let path = Path::new(dir).join(format!("{stem}-{}-{}.sol", result.seed, result.trial));
+    debug!("Writing solution to {}", path.display());
+
+    let write = || -> io::Result<()> {
+        let mut out = io::BufWriter::new(fs::File::create(&path)?);
+        writeln!(out, "# graph: {}", result.graph_name)?;
+        writeln!(out, "# seed: {}", result.seed)?;
+        writeln!(out, "# trial: {}", result.trial)?;
+        utils::write_labeling(&mut out, &result.best, vertices)?;
+        out.into_inner()
+            .map_err(io::IntoInnerError::into_error)?
+            .sync_data()
+    };
+    write().map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!("failed to write the solution '{}': {e}", path.display()),
+        )
+    })
+}
+
+/// Destination of the per-generation trace. Like [`ResultOutput`], the file is appended to
+/// one trial at a time, so `--resume` keeps the trace of the trials already done. Files ending
+/// in `.jsonl` or `.json` get one JSON object per line; any other file gets CSV.
+struct TraceOutput {
+    file: fs::File,
+    json: bool,
+}
+
+impl TraceOutput {
+    /// Opens `path` for appending, writing the CSV header to a new file. A partially written
+    /// last row, left by an interrupted run, is dropped first.
+    fn open(path: &str) -> io::Result<Self> {
+        let json = Path::new(path).extension().is_some_and(|ext| {
+            ext.eq_ignore_ascii_case("jsonl") || ext.eq_ignore_ascii_case("json")
+        });
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(path)?;
+        drop_partial_row(&mut file)?;
+
+        if !json && file.metadata()?.len() == 0 {
+            file.write_all(
+                b"graph_name,trial,seed,generation,evaluations,best,mean,worst,median,diversity,\
+                  repaired,elapsed_time(microsecond)\n",
+            )?;
+            file.sync_data()?;
+        }
+
+        Ok(Self { file, json })
+    }
+
+    /// Appends the trace of `result`, in a single write.
+    fn write(&mut self, result: &TrialResult) -> io::Result<()> {
+        let mut text = String::new();
+        for row in &result.trace {
+            let f = &row.fitness;
+            let line = if self.json {
+                format!(
+                    "{{\"graph_name\":{},\"trial\":{},\"seed\":{},\"generation\":{},\
+                     \"evaluations\":{},\"best\":{},\"mean\":{:?},\"worst\":{},\"median\":{:?},\
+                     \"diversity\":{:?},\"repaired\":{},\"elapsed_time\":{}}}\n",
+                    serde_json::Value::from(result.graph_name.as_str()),
+                    result.trial,
+                    result.seed,
+                    row.generation,
+                    row.evaluations,
+                    f.best,
+                    f.mean,
+                    f.worst,
+                    f.median,
+                    row.diversity,
+                    row.repaired,
+                    row.elapsed_micros
+                )
+            } else {
+                format!(
+                    "{},{},{},{},{},{},{:?},{},{:?},{:?},{},{}\n",
+                    result.graph_name,
+                    result.trial,
+                    result.seed,
+                    row.generation,
+                    row.evaluations,
+                    f.best,
+                    f.mean,
+                    f.worst,
+                    f.median,
+                    row.diversity,
+                    row.repaired,
+                    row.elapsed_micros
+                )
+            };
+            text.push_str(&line);
         }
 
-        let elapsed_time = trial_start.elapsed();
-        let graph_name = file_path.split('/').last().unwrap_or("unknown").to_string();
+        self.file.write_all(text.as_bytes())?;
+        self.file.sync_data()
+    }
+}
+
+fn graph_name(file_path: &str) -> String {
+    Path::new(file_path).file_name().map_or_else(
+        || "unknown".to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    )
+}
+
+/// Returns the settings of the run `checkpoint` was saved by, refusing a checkpoint of another
+/// graph or of other settings than `config`, the base seed and the trials run aside.
+fn restored_config(
+    checkpoint: &Checkpoint,
+    graph_name: &str,
+    config: &ExperimentConfig,
+) -> Result<ExperimentConfig, String> {
+    if checkpoint.graph != graph_name {
+        return Err(format!(
+            "the checkpoint is of the graph '{}', not '{graph_name}'",
+            checkpoint.graph
+        ));
+    }
+
+    let saved = ExperimentConfig::from_toml(&checkpoint.config, ExperimentConfig::default())
+        .map_err(|e| format!("invalid settings in the checkpoint: {e}"))?;
+    let config = ExperimentConfig {
+        trials: saved.trials,
+        trial: saved.trial,
+        seed: Some(checkpoint.seed),
+        ..config.clone()
+    };
+    if config.to_toml() != checkpoint.config {
+        return Err(format!(
+            "the checkpoint was saved with other settings; restore it with these:\n{}",
+            checkpoint.config
+        ));
+    }
+    Ok(config)
+}
+
+fn solve(args: &SolveArgs) -> Result<ExitCode, String> {
+    let name = graph_name(&args.graph);
+    let restore = args
+        .restore
+        .as_deref()
+        .map(|path| {
+            Checkpoint::load(path)
+                .map_err(|e| format!("failed to read the checkpoint '{path}': {e}"))
+        })
+        .transpose()?;
+    let done = match (&args.output, args.resume) {
+        (Some(path), true) => completed_trials(path)?,
+        _ => DoneTrials::new(),
+    };
+    let config = match &restore {
+        Some(checkpoint) => restored_config(checkpoint, &name, &args.config)?,
+        None => resumed_seed(&args.config, &done),
+    };
+    info!(
+        "Solve - File: {}, Trials: {}, Output: {:?}, Resume: {}, Restore: {:?}",
+        args.graph, config.trials, args.output, args.resume, args.restore
+    );
+    info!("Resolved config:\n{}", config.to_toml());
 
-        info!(
-            "Trial {} completed - Final fitness: {}, Time: {:?}",
-            trial + 1,
-            best_solution.fitness(),
-            elapsed_time
+    let pending = match &restore {
+        Some(checkpoint) => vec![checkpoint.trial],
+        None => pending_trials(&config, &name, &done),
+    };
+    let planned = config.trial_numbers().count();
+    if args.resume && pending.len() < planned {
+        eprintln!(
+            "Resuming: {} of {planned} trials already done.",
+            planned - pending.len()
         );
+        if pending.is_empty() {
+            return Ok(ExitCode::SUCCESS);
+        }
+    }
 
-        results.lock().unwrap().push(TrialResult {
-            graph_name,
-            node_count: graph.order(),
-            edge_count: graph.edge_count(),
-            fitness: best_solution.fitness(),
-            elapsed_micros: elapsed_time.as_micros(),
-        });
-    });
+    let start_time = Instant::now();
+    let (graph, vertices, dropped) = load_feasible_graph(&args.graph, args.drop_isolated)?;
+    if let Some(checkpoint) = &restore {
+        let order = checkpoint.best.genes().len();
+        if order != graph.order() {
+            return Err(format!(
+                "the checkpoint labels {order} vertices, but the graph has {}",
+                graph.order()
+            ));
+        }
+        eprintln!(
+            "Restoring trial {} (seed {}) at generation {}.",
+            checkpoint.trial, checkpoint.seed, checkpoint.generation
+        );
+    }
+    for dir in [&args.checkpoint, &args.solutions].into_iter().flatten() {
+        fs::create_dir_all(dir).map_err(|e| format!("failed to create '{dir}': {e}"))?;
+    }
 
-    let results = results.into_inner().unwrap();
-    if let Err(e) = write_results_to_csv(&results, &output_file) {
+    let write_error = |e: io::Error| {
         error!("Failed to write results: {}", e);
-        eprintln!("Failed to write results to file: {}", e);
-        exit(1);
-    }
+        format!("failed to write results: {e}")
+    };
+    let output =
+        Mutex::new(ResultOutput::open(args.output.as_deref(), &config).map_err(write_error)?);
+    let options = RunOptions {
+        trace: args.trace.is_some(),
+        checkpoints: args
+            .checkpoint
+            .as_deref()
+            .map(|dir| (dir, args.checkpoint_every)),
+        restore: restore.as_ref(),
+    };
+    let trace = match &args.trace {
+        Some(path) => Some(Mutex::new(TraceOutput::open(path).map_err(|e| {
+            error!("Failed to open trace: {}", e);
+            format!("failed to open the trace '{path}': {e}")
+        })?)),
+        None => None,
+    };
+    run_trials(&graph, &name, &config, &pending, &options, &|result| {
+        // O traço e a solução vêm antes da linha de resultado, que marca a execução como
+        // concluída.
+        if let Some(trace) = &trace {
+            trace.lock().expect("a trial panicked").write(result)?;
+        }
+        if let Some(dir) = &args.solutions {
+            write_solution(result, &vertices, &args.graph, dir)?;
+        }
+        output
+            .lock()
+            .expect("a trial panicked")
+            .write(result, dropped)
+    })
+    .map_err(|e| {
+        error!("Failed to run trials: {}", e);
+        e.to_string()
+    })?;
 
     let total_time = start_time.elapsed();
     info!(
         "Execution completed in {:.2} seconds",
         total_time.as_secs_f64()
     );
-    println!(
+    eprintln!(
         "Execution completed in {:.2} seconds.",
         total_time.as_secs_f64()
     );
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Returns the results file of `file` in a batch, `<output-dir>/<graph>.csv`.
+fn instance_output(output_dir: &str, file: &str) -> String {
+    let stem = Path::new(file)
+        .file_stem()
+        .map_or_else(|| "graph".into(), |stem| stem.to_string_lossy());
+    Path::new(output_dir)
+        .join(format!("{stem}.csv"))
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Solves the trials of one graph of a batch that are in neither `done`, the trials already in
+/// `output`, nor `combined_done`, the ones already in `combined`, appending their rows to both
+/// files. Returns the order, the size and the best weight found, or `None` when every trial
+/// was already done.
+///
+/// Each row goes to `output` first and to `combined` next, so a run stopped between the two
+/// writes leaves the trial in `output` only. A trial in just one of the files counts as done
+/// and its row is copied to the other.
+fn solve_instance(
+    file: &str,
+    output: &str,
+    done: &DoneTrials,
+    config: &ExperimentConfig,
+    args: &BatchArgs,
+    combined: &Mutex<ResultOutput>,
+    combined_done: &DoneTrials,
+) -> Result<Option<(usize, usize, usize)>, String> {
+    let name = graph_name(file);
+    let write_error = |e: io::Error| format!("failed to write results to '{output}': {e}");
+
+    for (key, row) in done {
+        if !combined_done.contains_key(key) {
+            info!(
+                "Copying trial {} of {} to the combined results",
+                key.1, key.0
+            );
+            combined
+                .lock()
+                .expect("a trial panicked")
+                .copy(row)
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    let missing: Vec<&String> = combined_done
+        .iter()
+        .filter(|&(key, _)| key.0 == name && !done.contains_key(key))
+        .map(|(_, row)| row)
+        .collect();
+
+    let mut all_done = done.clone();
+    all_done.extend(
+        combined_done
+            .iter()
+            .filter(|(key, _)| key.0 == name)
+            .map(|(key, row)| (key.clone(), row.clone())),
+    );
+    let pending = pending_trials(config, &name, &all_done);
+    if pending.is_empty() && missing.is_empty() {
+        return Ok(None);
+    }
+
+    let mut instance = ResultOutput::open(Some(output), config).map_err(write_error)?;
+    for row in missing {
+        info!("Copying a trial of {} from the combined results", name);
+        instance.copy(row).map_err(write_error)?;
+    }
+    if pending.is_empty() {
+        return Ok(None);
+    }
+
+    let (graph, vertices, dropped) = load_feasible_graph(file, args.drop_isolated)?;
+    let instance = Mutex::new(instance);
+    let results = run_trials(
+        &graph,
+        &name,
+        config,
+        &pending,
+        &RunOptions::default(),
+        &|result| {
+            if let Some(dir) = &args.solutions {
+                write_solution(result, &vertices, file, dir)?;
+            }
+            instance
+                .lock()
+                .expect("a trial panicked")
+                .write(result, dropped)?;
+            combined
+                .lock()
+                .expect("a trial panicked")
+                .write(result, dropped)
+        },
+    )
+    .map_err(|e| e.to_string())?;
+
+    let best = results
+        .iter()
+        .map(|result| result.fitness)
+        .min()
+        .unwrap_or(0);
+    Ok(Some((graph.order(), graph.edge_count(), best)))
+}
+
+fn batch(args: &BatchArgs) -> Result<ExitCode, String> {
+    let files = batch::instance_files(&args.instances)?;
+    if files.is_empty() {
+        return Err(format!(
+            "no graph files found in '{}'",
+            args.instances.join(",")
+        ));
+    }
+    let outputs: Vec<String> = files
+        .iter()
+        .map(|file| instance_output(&args.output_dir, file))
+        .collect();
+    // Grafos com o mesmo nome sem extensão gravariam no mesmo arquivo.
+    let mut owners: HashMap<&str, &str> = HashMap::new();
+    for (file, output) in files.iter().zip(&outputs) {
+        if let Some(other) = owners.insert(output, file) {
+            return Err(format!(
+                "'{other}' and '{file}' would both write '{output}'; rename one of them or \
+                 run them in separate batches"
+            ));
+        }
+    }
+    let combined = args
+        .combined
+        .clone()
+        .unwrap_or_else(|| format!("{}.csv", args.output_dir.trim_end_matches('/')));
+    let (done, combined_done): (Vec<DoneTrials>, DoneTrials) = if args.resume {
+        let done = outputs
+            .iter()
+            .map(|output| completed_trials(output))
+            .collect::<Result<_, _>>()?;
+        (done, completed_trials(&combined)?)
+    } else {
+        (vec![DoneTrials::new(); files.len()], DoneTrials::new())
+    };
+    let mut all_done = combined_done.clone();
+    all_done.extend(done.iter().flatten().map(|(k, v)| (k.clone(), v.clone())));
+    let config = resumed_seed(&args.config, &all_done);
+
+    for dir in [Some(&args.output_dir), args.solutions.as_ref()]
+        .into_iter()
+        .flatten()
+    {
+        fs::create_dir_all(dir).map_err(|e| format!("failed to create '{dir}': {e}"))?;
+    }
+    let combined_output = Mutex::new(
+        ResultOutput::open(Some(&combined), &config)
+            .map_err(|e| format!("failed to write results to '{combined}': {e}"))?,
+    );
+
+    // Um único pool atende todos os grafos, em vez de um por processo como no script antigo.
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(args.threads)
+        .build()
+        .map_err(|e| format!("failed to start the thread pool: {e}"))?;
+    info!(
+        "Batch - Graphs: {}, Trials: {}, Threads: {}, Output: {}, Combined: {}, Resume: {}",
+        files.len(),
+        config.trials,
+        pool.current_num_threads(),
+        args.output_dir,
+        combined,
+        args.resume
+    );
+    info!("Resolved config:\n{}", config.to_toml());
+
+    let start_time = Instant::now();
+    let mut failed = 0;
+    for (idx, file) in files.iter().enumerate() {
+        eprintln!("[{}/{}] {}", idx + 1, files.len(), graph_name(file));
+        let instance_start = Instant::now();
+        let outcome = pool.install(|| {
+            solve_instance(
+                file,
+                &outputs[idx],
+                &done[idx],
+                &config,
+                args,
+                &combined_output,
+                &combined_done,
+            )
+        });
+        match outcome {
+            Ok(Some((order, size, best))) => eprintln!(
+                "  order {order}, size {size}, best fitness {best} ({:.2} seconds)",
+                instance_start.elapsed().as_secs_f64()
+            ),
+            Ok(None) => eprintln!(
+                "  all {} trials already done",
+                config.trial_numbers().count()
+            ),
+            Err(e) => {
+                error!("Failed to solve {}: {}", file, e);
+                eprintln!("  error: {e}");
+                failed += 1;
+            }
+        }
+    }
+
+    let total_time = start_time.elapsed();
+    info!(
+        "Batch completed in {:.2} seconds, {} of {} graphs failed",
+        total_time.as_secs_f64(),
+        failed,
+        files.len()
+    );
+    eprintln!(
+        "Processed {} graphs ({failed} failed) in {:.2} seconds.",
+        files.len(),
+        total_time.as_secs_f64()
+    );
+
+    Ok(if failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+fn bench(args: &BenchArgs) -> Result<ExitCode, String> {
+    let (graph, _, _) = load_feasible_graph(&args.graph, args.drop_isolated)?;
+    let config = with_resolved_seed(&args.config);
+    let trials: Vec<usize> = config.trial_numbers().collect();
+    let mut results = run_trials(
+        &graph,
+        &graph_name(&args.graph),
+        &config,
+        &trials,
+        &RunOptions::default(),
+        &|_| Ok(()),
+    )
+    .map_err(|e| e.to_string())?;
+    results.sort_by_key(|result| result.elapsed_micros);
+
+    let micros: Vec<u128> = results.iter().map(|result| result.elapsed_micros).collect();
+    let total: u128 = micros.iter().sum();
+    let best = results
+        .iter()
+        .map(|result| result.fitness)
+        .min()
+        .unwrap_or(0);
+
+    println!(
+        "{}: order {}, size {}, {} trials",
+        graph_name(&args.graph),
+        graph.order(),
+        graph.edge_count(),
+        results.len()
+    );
+    println!("best fitness: {best}");
+    println!(
+        "time per trial (ms): min {:.3}, median {:.3}, mean {:.3}, max {:.3}",
+        micros[0] as f64 / 1e3,
+        micros[micros.len() / 2] as f64 / 1e3,
+        total as f64 / micros.len() as f64 / 1e3,
+        micros[micros.len() - 1] as f64 / 1e3
+    );
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn validate(args: &ValidateArgs) -> Result<ExitCode, String> {
+    let (graph, vertices) = load_graph(&args.graph)?;
+    let (labeling, unlabeled) = utils::read_labeling(&args.labeling, &vertices)
+        .map_err(|e| format!("failed to read labeling: {e}"))?;
+    let violations = labeling.violations(&graph);
+    let [v0, v1, v2] = labeling.label_counts();
+
+    println!(
+        "graph: {} (order {}, size {})",
+        graph_name(&args.graph),
+        graph.order(),
+        graph.edge_count()
+    );
+    println!("weight: {}", labeling.fitness());
+    println!("|V0|: {v0}, |V1|: {v1}, |V2|: {v2}");
+    if !unlabeled.is_empty() {
+        println!("unlabeled vertices, taken as 0: {}", unlabeled.len());
+    }
+
+    if violations.is_empty() {
+        println!("valid total Roman dominating function");
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    println!("invalid: {} violated constraints", violations.len());
+    for violation in violations {
+        let violation = violation.map_vertex(|v| vertices.original(v).unwrap_or(v));
+        println!("  {violation}");
+    }
+
+    Ok(ExitCode::FAILURE)
+}
+
+fn exact(args: &ExactArgs) -> Result<ExitCode, String> {
+    let (graph, vertices) = load_graph(&args.graph)?;
+    println!(
+        "graph: {} (order {}, size {})",
+        graph_name(&args.graph),
+        graph.order(),
+        graph.edge_count()
+    );
+
+    let solver = match args.node_limit {
+        Some(limit) => ExactSolver::new().with_node_limit(limit),
+        None => ExactSolver::new(),
+    };
+    let start_time = Instant::now();
+    let solution = match solver.solve(&graph) {
+        Ok(solution) => solution,
+        Err(e) => {
+            println!("Infeasible: {e}");
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+    info!(
+        "Branch and bound - Nodes: {}, Time: {:?}",
+        solution.nodes(),
+        start_time.elapsed()
+    );
+
+    if solution.is_optimal() {
+        println!("optimum: {}", solution.weight());
+    } else {
+        // O limite de nós interrompeu a busca antes de fechar o intervalo.
+        println!(
+            "best weight: {}, lower bound: {} (node limit reached)",
+            solution.weight(),
+            solution.lower_bound()
+        );
+    }
+    println!(
+        "nodes: {}, time: {:.2} seconds",
+        solution.nodes(),
+        start_time.elapsed().as_secs_f64()
+    );
+
+    if let Some(path) = &args.solution {
+        let write = || -> io::Result<()> {
+            let mut out = io::BufWriter::new(fs::File::create(path)?);
+            writeln!(out, "# graph: {}", graph_name(&args.graph))?;
+            utils::write_labeling(&mut out, solution.chromosome(), &vertices)?;
+            out.flush()
+        };
+        write().map_err(|e| format!("failed to write the solution to '{path}': {e}"))?;
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn report(args: &ReportArgs) -> Result<ExitCode, String> {
+    let files = report::result_files(&args.results)?;
+    let optima = match &args.optima {
+        Some(path) => report::read_optima(path)?,
+        None => Default::default(),
+    };
+    let summaries = report::summarize(&files, &optima)?;
+    if summaries.is_empty() {
+        return Err(format!("no trials found in {} result file(s)", files.len()));
+    }
+
+    info!(
+        "Summarized {} graphs from {} result files as {}",
+        summaries.len(),
+        files.len(),
+        args.format.name()
+    );
+    let table = report::render(&summaries, args.format);
+    match &args.output {
+        Some(path) => {
+            fs::write(path, table).map_err(|e| format!("failed to write '{path}': {e}"))?
+        }
+        None => print!("{table}"),
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn info(args: &InfoArgs) -> Result<ExitCode, String> {
+    let (graph, _) = load_graph(&args.graph)?;
+    let degrees: Vec<usize> = graph
+        .vertices()
+        .map(|v| graph.degree(v).unwrap_or(0))
+        .collect();
+
+    let order = graph.order();
+    let size = graph.edge_count();
+    let isolated = degrees.iter().filter(|&&d| d == 0).count();
+    let density = if order > 1 {
+        2.0 * size as f64 / (order * (order - 1)) as f64
+    } else {
+        0.0
+    };
+
+    println!("graph: {}", graph_name(&args.graph));
+    println!("order: {order}");
+    println!("size: {size}");
+    println!("density: {density:.4}");
+    println!(
+        "degree: min {}, max {}, mean {:.2}",
+        degrees.iter().min().unwrap_or(&0),
+        degrees.iter().max().unwrap_or(&0),
+        2.0 * size as f64 / order as f64
+    );
+    println!("isolated vertices: {isolated}");
+    println!(
+        "components: {}",
+        Feasibility::analyze(&graph).components().len()
+    );
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn main() -> ExitCode {
+    if let Err(e) = setup_logger() {
+        eprintln!("Failed to setup logger: {}", e);
+        return ExitCode::FAILURE;
+    }
+
+    let command = match cli::parse(env::args_os()) {
+        Ok(command) => command,
+        Err(e) => {
+            // A ajuda também chega aqui, e o clap a imprime em stdout com código 0.
+            if e.use_stderr() {
+                error!("Error parsing arguments: {}", e);
+            }
+            e.exit()
+        }
+    };
+
+    info!("Starting execution: {:?}", command);
+
+    let result = match &command {
+        Command::Solve(args) => solve(args),
+        Command::Batch(args) => batch(args),
+        Command::Bench(args) => bench(args),
+        Command::Report(args) => report(args),
+        Command::Validate(args) => validate(args),
+        Command::Exact(args) => exact(args),
+        Command::Info(args) => info(args),
+    };
+
+    result.unwrap_or_else(|e| {
+        error!("{}", e);
+        eprintln!("error: {e}");
+        ExitCode::FAILURE
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BIN: &str = env!("CARGO_PKG_NAME");
+
+    /// Returns a path in the temporary directory, removing what an earlier run left there.
+    fn scratch(name: &str) -> String {
+        let path = env::temp_dir().join(format!("{BIN}-main-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        let _ = fs::remove_file(&path);
+        path.to_string_lossy().into_owned()
+    }
+
+    /// Writes a cycle of 12 vertices with a few chords.
+    fn graph(name: &str) -> String {
+        let path = scratch(name);
+        let mut edges: String = (1..=12).map(|u| format!("{u} {}\n", u % 12 + 1)).collect();
+        edges += "1 7\n2 9\n4 11\n";
+        fs::write(&path, edges).unwrap();
+        path
+    }
+
+    fn solve_args(args: &[&str]) -> SolveArgs {
+        match cli::parse([BIN, "solve"].into_iter().chain(args.iter().copied())) {
+            Ok(Command::Solve(args)) => args,
+            other => panic!("{other:?}"),
+        }
+    }
+
+    /// Returns the rows of a results file keyed by trial, without their elapsed time.
+    fn rows(path: &str) -> HashMap<usize, String> {
+        fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .filter(|line| !line.starts_with('#') && *line != RESULTS_HEADER)
+            .map(|line| {
+                let mut fields: Vec<&str> = line.split(',').collect();
+                let trial = fields[6].parse().unwrap();
+                fields.remove(4);
+                (trial, fields.join(","))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn a_single_trial_gives_its_row_of_the_full_run() {
+        let graph = graph("single-trial.txt");
+        let full = scratch("single-trial-full.csv");
+        let one = scratch("single-trial-one.csv");
+        let options = [
+            "-g",
+            &graph,
+            "-n",
+            "3",
+            "--seed",
+            "11",
+            "--generations",
+            "20",
+        ];
+
+        let args: Vec<&str> = options.iter().copied().chain(["-o", &full]).collect();
+        solve(&solve_args(&args)).unwrap();
+        let args: Vec<&str> = options
+            .iter()
+            .copied()
+            .chain(["--trial", "2", "-o", &one])
+            .collect();
+        solve(&solve_args(&args)).unwrap();
+
+        let full_rows = rows(&full);
+        assert_eq!(full_rows.len(), 3);
+        assert_eq!(rows(&one), HashMap::from([(2, full_rows[&2].clone())]));
+        assert!(fs::read_to_string(&one)
+            .unwrap()
+            .lines()
+            .any(|line| line == "# trial = 2"));
+    }
+
+    #[test]
+    fn an_interrupted_run_keeps_the_solutions_of_its_rows() {
+        let graph = graph("interrupted.txt");
+        let output = scratch("interrupted.csv");
+        let dir = scratch("interrupted-solutions");
+        let stem = Path::new(&graph).file_stem().unwrap().to_string_lossy();
+        let solution = |trial: usize| Path::new(&dir).join(format!("{stem}-5-{trial}.sol"));
+        let options = [
+            "-g",
+            &graph,
+            "-n",
+            "3",
+            "--seed",
+            "5",
+            "--generations",
+            "20",
+            "-o",
+            &output,
+            "--solutions",
+            &dir,
+        ];
+
+        // Um diretório no lugar da solução da execução 2 faz a sua gravação falhar. Com uma
+        // única thread, a execução 1 termina antes.
+        fs::create_dir_all(solution(2)).unwrap();
+        let pool = ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+        let err = pool.install(|| solve(&solve_args(&options))).unwrap_err();
+        assert!(err.contains("failed to write the solution"), "{err}");
+
+        let done = rows(&output);
+        assert!(done.contains_key(&1), "{done:?}");
+        assert!(!done.contains_key(&2), "{done:?}");
+        for trial in done.keys() {
+            assert!(solution(*trial).is_file(), "trial {trial}");
+        }
+
+        fs::remove_dir(solution(2)).unwrap();
+        let args: Vec<&str> = options.iter().copied().chain(["--resume"]).collect();
+        solve(&solve_args(&args)).unwrap();
+        assert_eq!(rows(&output).len(), 3);
+        for trial in 1..=3 {
+            assert!(solution(trial).is_file(), "trial {trial}");
+        }
+    }
 }
@@ -0,0 +1,389 @@
+use std::{collections::HashMap, fs, path::Path, str::FromStr};
+
+use crate::config::ReportFormat;
+
+/// One row of a results CSV written by `solve`.
+#[derive(Debug, Clone)]
+struct Trial {
+    graph: String,
+    order: usize,
+    size: usize,
+    fitness: usize,
+    elapsed_micros: u128,
+}
+
+/// Statistics of all the trials of one graph.
+#[derive(Debug, Clone)]
+pub struct GraphSummary {
+    pub graph: String,
+    pub order: usize,
+    pub size: usize,
+    pub trials: usize,
+    pub best: usize,
+    pub worst: usize,
+    pub mean: f64,
+    /// Sample standard deviation, `0` for a single trial.
+    pub std_dev: f64,
+    pub median: f64,
+    /// Fraction of trials that reached the known optimum, if one was given.
+    pub success_rate: Option<f64>,
+    pub mean_time_secs: f64,
+}
+
+/// Expands `paths` into result files: files are kept, directories give their `.csv` files.
+pub fn result_files(paths: &[String]) -> Result<Vec<String>, String> {
+    let mut files = Vec::new();
+    for path in paths {
+        let meta = fs::metadata(path).map_err(|e| format!("failed to read '{path}': {e}"))?;
+        if !meta.is_dir() {
+            files.push(path.clone());
+            continue;
+        }
+
+        let mut csvs: Vec<String> = fs::read_dir(path)
+            .map_err(|e| format!("failed to read '{path}': {e}"))?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|p| {
+                p.is_file()
+                    && p.extension()
+                        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+            })
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect();
+        csvs.sort();
+        files.extend(csvs);
+    }
+
+    Ok(files)
+}
+
+/// Reads the trials of a results CSV. Comment lines (the echoed config) and repeated headers
+/// are skipped; columns are found by name, so files without the `seed` column are accepted.
+fn read_trials(path: &str) -> Result<Vec<Trial>, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("failed to read '{path}': {e}"))?;
+
+    let mut header: Option<(&str, [usize; 5])> = None;
+    let mut trials = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let Some((header_line, columns)) = header else {
+            let names: Vec<&str> = line.split(',').map(str::trim).collect();
+            let mut columns = [0; 5];
+            let wanted = [
+                "graph_name",
+                "graph_order",
+                "graph_size",
+                "fitness_value",
+                "elapsed_time(microsecond)",
+            ];
+            for (column, name) in columns.iter_mut().zip(wanted) {
+                *column = names.iter().position(|&n| n == name).ok_or_else(|| {
+                    format!(
+                        "{path}:{}: missing column '{name}' in the header",
+                        number + 1
+                    )
+                })?;
+            }
+            header = Some((line, columns));
+            continue;
+        };
+        if line == header_line {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let field = |idx: usize| fields.get(columns[idx]).copied().unwrap_or("");
+        let location = format!("{path}:{}", number + 1);
+        trials.push(Trial {
+            graph: field(0).to_string(),
+            order: parse_field(&location, "graph_order", field(1))?,
+            size: parse_field(&location, "graph_size", field(2))?,
+            fitness: parse_field(&location, "fitness_value", field(3))?,
+            elapsed_micros: parse_field(&location, "elapsed_time", field(4))?,
+        });
+    }
+
+    if header.is_none() {
+        return Err(format!("{path}: no header line found"));
+    }
+    Ok(trials)
+}
+
+fn parse_field<T: FromStr>(location: &str, name: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{location}: invalid {name} '{value}'"))
+}
+
+/// Reads known optima, one `graph value` pair per line. Empty lines and `#` comments are
+/// skipped.
+pub fn read_optima(path: &str) -> Result<HashMap<String, usize>, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("failed to read '{path}': {e}"))?;
+
+    let mut optima = HashMap::new();
+    for (number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        match line.split_whitespace().collect::<Vec<_>>()[..] {
+            [graph, value] => {
+                let value = value
+                    .parse()
+                    .map_err(|_| format!("{path}:{}: invalid optimum '{value}'", number + 1))?;
+                optima.insert(graph.to_string(), value);
+            }
+            _ => {
+                return Err(format!(
+                    "{path}:{}: expected 'graph optimum', found '{line}'",
+                    number + 1
+                ))
+            }
+        }
+    }
+
+    Ok(optima)
+}
+
+/// Looks up the optimum of `graph` by its file name (`C125-9.txt`) or its stem (`C125-9`).
+fn optimum(optima: &HashMap<String, usize>, graph: &str) -> Option<usize> {
+    optima.get(graph).copied().or_else(|| {
+        let stem = Path::new(graph).file_stem()?.to_str()?;
+        optima.get(stem).copied()
+    })
+}
+
+/// Groups the trials of `files` by graph, in order of first appearance, and summarizes them.
+pub fn summarize(
+    files: &[String],
+    optima: &HashMap<String, usize>,
+) -> Result<Vec<GraphSummary>, String> {
+    let mut groups: Vec<(String, Vec<Trial>)> = Vec::new();
+    for file in files {
+        for trial in read_trials(file)? {
+            match groups.iter_mut().find(|(graph, _)| *graph == trial.graph) {
+                Some((_, trials)) => trials.push(trial),
+                None => groups.push((trial.graph.clone(), vec![trial])),
+            }
+        }
+    }
+
+    Ok(groups
+        .into_iter()
+        .map(|(graph, trials)| {
+            let optimum = optimum(optima, &graph);
+            summary(graph, &trials, optimum)
+        })
+        .collect())
+}
+
+fn summary(graph: String, trials: &[Trial], optimum: Option<usize>) -> GraphSummary {
+    let mut fitness: Vec<usize> = trials.iter().map(|t| t.fitness).collect();
+    fitness.sort_unstable();
+
+    let n = fitness.len();
+    let mean = fitness.iter().sum::<usize>() as f64 / n as f64;
+    let std_dev = if n > 1 {
+        let squares: f64 = fitness.iter().map(|&f| (f as f64 - mean).powi(2)).sum();
+        (squares / (n - 1) as f64).sqrt()
+    } else {
+        0.0
+    };
+    let successes = |opt: usize| fitness.iter().filter(|&&f| f <= opt).count();
+    let total_micros: u128 = trials.iter().map(|t| t.elapsed_micros).sum();
+
+    GraphSummary {
+        graph,
+        order: trials[0].order,
+        size: trials[0].size,
+        trials: n,
+        best: fitness[0],
+        worst: fitness[n - 1],
+        mean,
+        std_dev,
+        median: (fitness[(n - 1) / 2] + fitness[n / 2]) as f64 / 2.0,
+        success_rate: optimum.map(|opt| successes(opt) as f64 / n as f64),
+        mean_time_secs: total_micros as f64 / n as f64 / 1e6,
+    }
+}
+
+/// Renders the summaries as a table in `format`.
+pub fn render(summaries: &[GraphSummary], format: ReportFormat) -> String {
+    match format {
+        ReportFormat::Csv => render_csv(summaries),
+        ReportFormat::Markdown => render_markdown(summaries),
+        ReportFormat::Latex => render_latex(summaries),
+    }
+}
+
+fn render_csv(summaries: &[GraphSummary]) -> String {
+    let mut out = String::from(
+        "graph_name,graph_order,graph_size,trials,best,worst,mean,std_dev,median,\
+         success_rate,mean_time(second)\n",
+    );
+    for s in summaries {
+        let success = s
+            .success_rate
+            .map(|r| format!("{r:.3}"))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{:.3},{:.3},{:.1},{success},{:.3}\n",
+            s.graph,
+            s.order,
+            s.size,
+            s.trials,
+            s.best,
+            s.worst,
+            s.mean,
+            s.std_dev,
+            s.median,
+            s.mean_time_secs
+        ));
+    }
+    out
+}
+
+/// Cells shared by the Markdown and LaTeX tables, with `percent` as the percent sign.
+fn cells(s: &GraphSummary, percent: &str) -> Vec<String> {
+    vec![
+        s.order.to_string(),
+        s.size.to_string(),
+        s.trials.to_string(),
+        s.best.to_string(),
+        s.worst.to_string(),
+        format!("{:.2}", s.mean),
+        format!("{:.2}", s.std_dev),
+        format!("{:.1}", s.median),
+        s.success_rate
+            .map_or_else(|| "-".to_string(), |r| format!("{:.1}{percent}", r * 100.0)),
+        format!("{:.3}", s.mean_time_secs),
+    ]
+}
+
+fn render_markdown(summaries: &[GraphSummary]) -> String {
+    let mut out = String::from(
+        "| Graph | n | m | Trials | Best | Worst | Mean | Std | Median | Success | Time (s) |\n\
+         |:--|--:|--:|--:|--:|--:|--:|--:|--:|--:|--:|\n",
+    );
+    for s in summaries {
+        let graph = s.graph.replace('|', "\\|");
+        out.push_str(&format!("| {graph} | {} |\n", cells(s, "%").join(" | ")));
+    }
+    out
+}
+
+fn render_latex(summaries: &[GraphSummary]) -> String {
+    let mut out = String::from(
+        "\\begin{tabular}{lrrrrrrrrrr}\n\
+         \\toprule\n\
+         Graph & $n$ & $m$ & Trials & Best & Worst & Mean & Std & Median & Success & Time (s) \\\\\n\
+         \\midrule\n",
+    );
+    for s in summaries {
+        out.push_str(&format!(
+            "{} & {} \\\\\n",
+            latex_escape(&s.graph),
+            cells(s, "\\%").join(" & ")
+        ));
+    }
+    out.push_str("\\bottomrule\n\\end{tabular}\n");
+    out
+}
+
+fn latex_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '_' | '%' | '&' | '#' | '$' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trials(fitness: &[usize]) -> Vec<Trial> {
+        fitness
+            .iter()
+            .map(|&fitness| Trial {
+                graph: "g.txt".to_string(),
+                order: 10,
+                size: 15,
+                fitness,
+                elapsed_micros: 2_000_000,
+            })
+            .collect()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn single_trial() {
+        let s = summary("g.txt".to_string(), &trials(&[7]), None);
+        assert_eq!((s.order, s.size, s.trials), (10, 15, 1));
+        assert_eq!((s.best, s.worst), (7, 7));
+        assert_close(s.mean, 7.0);
+        assert_close(s.std_dev, 0.0);
+        assert_close(s.median, 7.0);
+        assert_eq!(s.success_rate, None);
+        assert_close(s.mean_time_secs, 2.0);
+    }
+
+    #[test]
+    fn single_trial_with_optimum() {
+        let s = summary("g.txt".to_string(), &trials(&[7]), Some(7));
+        assert_eq!(s.success_rate, Some(1.0));
+        let s = summary("g.txt".to_string(), &trials(&[7]), Some(6));
+        assert_eq!(s.success_rate, Some(0.0));
+    }
+
+    #[test]
+    fn even_count_averages_the_middle_pair() {
+        let s = summary("g.txt".to_string(), &trials(&[9, 6, 8, 7]), None);
+        assert_eq!((s.trials, s.best, s.worst), (4, 6, 9));
+        assert_close(s.mean, 7.5);
+        assert_close(s.std_dev, (5.0_f64 / 3.0).sqrt());
+        assert_close(s.median, 7.5);
+        assert_eq!(s.success_rate, None);
+    }
+
+    #[test]
+    fn even_count_with_optimum() {
+        let s = summary("g.txt".to_string(), &trials(&[9, 6, 8, 6]), Some(6));
+        assert_close(s.median, 7.0);
+        assert_eq!(s.success_rate, Some(0.5));
+    }
+
+    #[test]
+    fn odd_count_takes_the_middle() {
+        let s = summary("g.txt".to_string(), &trials(&[8, 6, 10]), None);
+        assert_eq!((s.trials, s.best, s.worst), (3, 6, 10));
+        assert_close(s.mean, 8.0);
+        assert_close(s.std_dev, 2.0);
+        assert_close(s.median, 8.0);
+        assert_eq!(s.success_rate, None);
+    }
+
+    #[test]
+    fn odd_count_with_optimum() {
+        let s = summary("g.txt".to_string(), &trials(&[8, 6, 10]), Some(8));
+        assert_close(s.success_rate.unwrap(), 2.0 / 3.0);
+    }
+}
@@ -1,11 +1,88 @@
 use std::{
     collections::HashMap,
     fs::File,
-    io::{self, BufRead},
+    io::{self, BufRead, Write},
+    path::Path,
 };
 
 use kambo_graph::{graphs::simple::UndirectedGraph, Graph, GraphMut};
 
+use crate::genetic::Chromosome;
+
+/// Bidirectional mapping between the vertex IDs of a graph file and the contiguous internal
+/// indices `0..n` used by the graph and the chromosomes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VertexMap {
+    originals: Vec<usize>,
+    internals: HashMap<usize, usize>,
+}
+
+impl VertexMap {
+    /// Creates an empty mapping.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the mapping `v -> v + offset` for the internal indices `0..order`.
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - The number of vertices.
+    /// * `offset` - The original ID of internal vertex `0` (`1` for DIMACS files).
+    #[must_use]
+    pub fn shifted(order: usize, offset: usize) -> Self {
+        let originals: Vec<usize> = (0..order).map(|v| v + offset).collect();
+        let internals = originals.iter().enumerate().map(|(v, &o)| (o, v)).collect();
+        Self {
+            originals,
+            internals,
+        }
+    }
+
+    /// Returns the internal index of `original`, assigning the next free index if it is new.
+    ///
+    /// # Arguments
+    ///
+    /// * `original` - A vertex ID as written in the graph file.
+    pub fn insert(&mut self, original: usize) -> usize {
+        *self.internals.entry(original).or_insert_with(|| {
+            self.originals.push(original);
+            self.originals.len() - 1
+        })
+    }
+
+    /// Returns the internal index of an original vertex ID, if the vertex exists.
+    #[must_use]
+    pub fn internal(&self, original: usize) -> Option<usize> {
+        self.internals.get(&original).copied()
+    }
+
+    /// Returns the original vertex ID of an internal index, if the vertex exists.
+    #[must_use]
+    pub fn original(&self, internal: usize) -> Option<usize> {
+        self.originals.get(internal).copied()
+    }
+
+    /// Returns the original IDs, indexed by internal index.
+    #[must_use]
+    pub fn originals(&self) -> &[usize] {
+        &self.originals
+    }
+
+    /// Returns the number of mapped vertices.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.originals.len()
+    }
+
+    /// Returns `true` if no vertex is mapped.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.originals.is_empty()
+    }
+}
+
 /// Builds an undirected graph from a file.
 ///
 /// # Arguments
@@ -13,85 +90,551 @@ use kambo_graph::{graphs::simple::UndirectedGraph, Graph, GraphMut};
 /// * `file_path` - The path to the file containing the graph edges.
 ///
 /// # File Format
-/// Each line in the file should represent an edge in the format `u v`, where `u` and `v` are vertices.
-/// Lines that are empty or start with `#` are ignored.
+/// See [`read_edge_list`].
 ///
 /// # Errors
 ///
 /// This function will panic if:
 /// - The file cannot be opened.
-/// - A line in the file does not have exactly two values.
+/// - A line in the file does not have one or two values.
 /// - A vertex cannot be parsed as an integer.
-/// - An edge cannot be added to the graph due to invalid vertices or duplicate edges.
-///
-/// The resulting graph will have the edges (1-2), (2-3), and (3-4).
+/// - An edge is a loop.
 ///
 /// # Panics
 /// This function panics if the input format is invalid.
 #[must_use]
 pub fn build_graph(file_path: &str) -> UndirectedGraph<usize> {
-    let file = File::open(file_path).expect("Failed to open the file");
+    match read_edge_list(file_path) {
+        Ok((graph, _)) => graph,
+        Err(err) => panic!("Failed to build the graph: {err}"),
+    }
+}
+
+/// Reads an edge-list file, returning the graph and the mapping to the IDs used in the file.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the file containing the graph edges.
+///
+/// # File Format
+/// - `u v`: an edge between vertices `u` and `v`.
+/// - `v`: declares vertex `v`, so vertices without edges are kept.
+///
+/// Lines that are empty or start with `#` are ignored. Vertices receive internal indices in the
+/// order they first appear, and repeated edges are added once.
+///
+/// # Errors
+///
+/// Returns an error of kind [`io::ErrorKind::InvalidData`] when a line is malformed, a vertex
+/// is not a non-negative integer or an edge is a loop. Other I/O errors are returned unchanged.
+pub fn read_edge_list(file_path: &str) -> io::Result<(UndirectedGraph<usize>, VertexMap)> {
+    let file = File::open(file_path)?;
     let reader = io::BufReader::new(file);
 
     let mut graph = UndirectedGraph::<usize>::new_undirected();
+    let mut vertices = VertexMap::new();
 
-    for line in reader.lines() {
-        let line = match line {
-            Ok(content) => content.trim().to_string(),
-            Err(err) => panic!("Error reading a line from the file: {err}"),
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        let invalid = |message: String| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{file_path}:{}: {message}", idx + 1),
+            )
         };
 
         if line.is_empty() || line.starts_with('#') {
             continue;
         }
 
+        let mut ids = Vec::with_capacity(2);
+        for part in line.split_whitespace() {
+            let original = part
+                .parse()
+                .map_err(|_| invalid(format!("invalid vertex '{part}'")))?;
+            ids.push(original);
+        }
+
+        match ids[..] {
+            [v] => {
+                graph.add_vertex(vertices.insert(v)).ok();
+            }
+            [u, v] if u == v => return Err(invalid(format!("loop on vertex {u}"))),
+            [u, v] => {
+                let (u, v) = (vertices.insert(u), vertices.insert(v));
+                graph.add_vertex(u).ok();
+                graph.add_vertex(v).ok();
+                if !graph.contains_edge(&u, &v) {
+                    graph
+                        .add_edge(&u, &v)
+                        .map_err(|_| invalid("failed to add edge".to_string()))?;
+                }
+            }
+            _ => return Err(invalid(format!("expected 'u v' or 'v', found '{line}'"))),
+        }
+    }
+
+    Ok((graph, vertices))
+}
+
+/// Reads a file in the DIMACS format used by the clique and coloring benchmarks (`.clq`,
+/// `.col`), returning the graph and the mapping to the IDs used in the file.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the DIMACS file.
+///
+/// # File Format
+/// - `c ...`: comment lines, ignored.
+/// - `p edge N M` (or `p col N M`): declares `N` vertices, numbered `1..=N`, and `M` edges.
+/// - `e u v`: an edge between vertices `u` and `v`.
+///
+/// Vertex `v` becomes vertex `v - 1` of the graph. Every declared vertex is kept, including the
+/// ones that appear in no edge. Repeated edges (such as `e 1 2` followed by `e 2 1`) are added
+/// once, and `M` may count either the `e` lines or the distinct edges.
+///
+/// # Errors
+///
+/// Returns an error of kind [`io::ErrorKind::InvalidData`] when:
+/// - An edge or an unknown line appears before the `p` line, or the `p` line is missing.
+/// - A line is malformed, or a vertex is outside `1..=N`.
+/// - An edge is a loop.
+/// - `M` is neither the number of `e` lines nor the number of distinct edges, which means
+///   the file is truncated or its header is wrong.
+///
+/// Other I/O errors are returned unchanged.
+pub fn read_dimacs(file_path: &str) -> io::Result<(UndirectedGraph<usize>, VertexMap)> {
+    let file = File::open(file_path)?;
+    let reader = io::BufReader::new(file);
+
+    let invalid = |line_number: usize, message: &str| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{file_path}:{line_number}: {message}"),
+        )
+    };
+
+    let mut graph: Option<UndirectedGraph<usize>> = None;
+    let mut order = 0;
+    let mut declared_edges = 0;
+    let mut edge_lines = 0;
+
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line_number = idx + 1;
         let parts: Vec<&str> = line.split_whitespace().collect();
-        assert!(parts.len() == 2, "Invalid format on line: {line}");
 
-        let u: usize = parts[0].parse().expect("Failed to parse vertex 'u'");
-        let v: usize = parts[1].parse().expect("Failed to parse vertex 'v'");
+        match parts.as_slice() {
+            [] | ["c", ..] => {}
+            ["p", _format, n, m] => {
+                if graph.is_some() {
+                    return Err(invalid(line_number, "repeated 'p' line"));
+                }
+                order = n
+                    .parse()
+                    .map_err(|_| invalid(line_number, "invalid vertex count"))?;
+                declared_edges = m
+                    .parse()
+                    .map_err(|_| invalid(line_number, "invalid edge count"))?;
 
-        graph.add_vertex(u).ok();
-        graph.add_vertex(v).ok();
-        graph.add_edge(&u, &v).expect("Failed to add edge");
+                let mut declared = UndirectedGraph::<usize>::new_undirected();
+                for v in 0..order {
+                    declared.add_vertex(v).ok();
+                }
+                graph = Some(declared);
+            }
+            ["e", u, v] => {
+                let Some(graph) = graph.as_mut() else {
+                    return Err(invalid(line_number, "edge before the 'p' line"));
+                };
+
+                let parse = |vertex: &str| -> io::Result<usize> {
+                    match vertex.parse::<usize>() {
+                        Ok(v) if (1..=order).contains(&v) => Ok(v - 1),
+                        _ => Err(invalid(
+                            line_number,
+                            &format!("vertex '{vertex}' is not in 1..={order}"),
+                        )),
+                    }
+                };
+                let (u, v) = (parse(u)?, parse(v)?);
+                edge_lines += 1;
+
+                if u == v {
+                    return Err(invalid(line_number, "loops are not supported"));
+                }
+                if !graph.contains_edge(&u, &v) {
+                    graph
+                        .add_edge(&u, &v)
+                        .map_err(|_| invalid(line_number, "failed to add edge"))?;
+                }
+            }
+            _ => return Err(invalid(line_number, &format!("unexpected line '{line}'"))),
+        }
     }
 
-    normalize_graph(&graph)
+    let graph = graph.ok_or_else(|| invalid(0, "missing 'p edge N M' line"))?;
+    if declared_edges != edge_lines && declared_edges != graph.edge_count() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "{file_path}: the 'p' line declares {declared_edges} edges, but the file has \
+                 {edge_lines} 'e' lines ({} distinct edges)",
+                graph.edge_count()
+            ),
+        ));
+    }
+    Ok((graph, VertexMap::shifted(order, 1)))
 }
 
-/// Normalizes the vertex indices of a graph to be contiguous from 0 to n-1.
+/// Reads a graph file in either supported format, chosen by [`is_dimacs`].
 ///
 /// # Arguments
 ///
-/// * `graph` - A mutable reference to an `UndirectedGraph<usize>` to normalize.
+/// * `file_path` - The path to the graph file.
 ///
 /// # Returns
-/// A new `UndirectedGraph<usize>` with normalized indices.
-fn normalize_graph(graph: &UndirectedGraph<usize>) -> UndirectedGraph<usize> {
-    let mut vertex_map = HashMap::new();
-    let mut new_index = 0;
-
-    // Cria o grafo normalizado
-    let mut normalized_graph = UndirectedGraph::<usize>::new_undirected();
-
-    for &vertex in graph.vertices() {
-        vertex_map.entry(vertex).or_insert_with(|| {
-            let current_index = new_index;
-            normalized_graph.add_vertex(new_index).unwrap();
-            new_index += 1;
-            current_index
-        });
-    }
-
-    for (u, neighbors) in graph.vertices().map(|v| (v, graph.neighbors(v).unwrap())) {
-        for &v in neighbors {
-            let new_u = vertex_map[u];
-            let new_v = vertex_map[&v];
-            if !normalized_graph.contains_edge(&new_u, &new_v) {
-                normalized_graph.add_edge(&new_u, &new_v).unwrap();
-            }
+/// The graph, with vertices `0..n`, and the mapping from those indices to the IDs used in the
+/// file, so results can be reported with the original labels.
+///
+/// # Errors
+///
+/// Returns the errors of [`read_dimacs`] or [`read_edge_list`].
+pub fn load_graph(file_path: &str) -> io::Result<(UndirectedGraph<usize>, VertexMap)> {
+    if is_dimacs(file_path)? {
+        read_dimacs(file_path)
+    } else {
+        read_edge_list(file_path)
+    }
+}
+
+/// Tells whether a graph file is in the DIMACS format.
+///
+/// Files with the `.clq`, `.col` or `.dimacs` extension are DIMACS files. Otherwise the first
+/// line that is not empty decides: DIMACS files start with a `c` comment or the `p` line.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the graph file.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened or read.
+pub fn is_dimacs(file_path: &str) -> io::Result<bool> {
+    let by_extension = Path::new(file_path).extension().is_some_and(|ext| {
+        ["clq", "col", "dimacs"]
+            .iter()
+            .any(|known| ext.eq_ignore_ascii_case(known))
+    });
+    if by_extension {
+        return Ok(true);
+    }
+
+    let reader = io::BufReader::new(File::open(file_path)?);
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if !line.is_empty() {
+            return Ok(line == "c" || line.starts_with("c ") || line.starts_with("p "));
+        }
+    }
+
+    Ok(false)
+}
+
+/// Writes a labeling in the solution format, one `vertex label` line per vertex.
+///
+/// Vertices are written with their original IDs, in internal order, after a `#` header with
+/// the weight and the sizes of `V0`, `V1` and `V2`. Lines starting with `#` are comments, so
+/// callers may add their own header lines before calling this function.
+///
+/// # Arguments
+///
+/// * `out` - The destination.
+/// * `chromosome` - The labeling, indexed by internal vertex.
+/// * `vertices` - The mapping to the original vertex IDs.
+///
+/// # Errors
+///
+/// Returns the errors of `out`.
+pub fn write_labeling<W: Write>(
+    out: &mut W,
+    chromosome: &Chromosome,
+    vertices: &VertexMap,
+) -> io::Result<()> {
+    let [v0, v1, v2] = chromosome.label_counts();
+    writeln!(out, "# weight: {}", chromosome.fitness())?;
+    writeln!(out, "# |V0|: {v0}, |V1|: {v1}, |V2|: {v2}")?;
+
+    for (internal, label) in chromosome.genes().iter().enumerate() {
+        let vertex = vertices.original(internal).unwrap_or(internal);
+        writeln!(out, "{vertex} {label}")?;
+    }
+
+    Ok(())
+}
+
+/// Reads a labeling in the solution format written by [`write_labeling`].
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the labeling file, made of `vertex label` lines with the vertex
+///   IDs of the graph file. Empty lines and `#` comments are skipped.
+/// * `vertices` - The mapping of the graph the labeling belongs to.
+///
+/// # Returns
+/// The labeling, indexed by internal vertex, and the internal indices of the vertices without
+/// a line, which are labeled `0`.
+///
+/// # Errors
+///
+/// Returns an error of kind [`io::ErrorKind::InvalidData`] when a line is malformed, or a vertex
+/// is not in the graph or is labeled more than once. Other I/O errors are returned unchanged.
+pub fn read_labeling(
+    file_path: &str,
+    vertices: &VertexMap,
+) -> io::Result<(Chromosome, Vec<usize>)> {
+    let reader = io::BufReader::new(File::open(file_path)?);
+
+    let mut genes = vec![0u8; vertices.len()];
+    let mut labeled = vec![false; vertices.len()];
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        let invalid = |message: String| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{file_path}:{}: {message}", idx + 1),
+            )
+        };
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let parsed = match line.split_whitespace().collect::<Vec<_>>()[..] {
+            [vertex, label] => vertex.parse::<usize>().ok().zip(label.parse::<u8>().ok()),
+            _ => None,
+        };
+        let (vertex, label) =
+            parsed.ok_or_else(|| invalid(format!("expected 'vertex label', found '{line}'")))?;
+
+        let Some(internal) = vertices.internal(vertex) else {
+            return Err(invalid(format!("vertex {vertex} is not in the graph")));
+        };
+        if labeled[internal] {
+            return Err(invalid(format!(
+                "vertex {vertex} is labeled more than once"
+            )));
         }
+        labeled[internal] = true;
+        genes[internal] = label;
     }
 
-    normalized_graph
+    let unlabeled = (0..vertices.len()).filter(|&v| !labeled[v]).collect();
+    Ok((Chromosome::new(genes), unlabeled))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes `content` to a file in the temporary directory and returns its path.
+    fn scratch(name: &str, content: &str) -> String {
+        let path =
+            std::env::temp_dir().join(format!("cl-total-rdga-utils-{}-{name}", std::process::id()));
+        std::fs::write(&path, content).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    fn error(result: io::Result<(UndirectedGraph<usize>, VertexMap)>) -> String {
+        let Err(err) = result else {
+            panic!("the file was accepted");
+        };
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        err.to_string()
+    }
+
+    #[test]
+    fn dimacs_keeps_isolated_vertices_and_shifts_ids() {
+        let path = scratch(
+            "isolated.clq",
+            "c five vertices, two of them isolated\np edge 5 3\ne 1 2\ne 2 3\ne 3 1\n",
+        );
+        let (graph, vertices) = read_dimacs(&path).unwrap();
+
+        assert_eq!(graph.order(), 5);
+        assert_eq!(graph.edge_count(), 3);
+        assert!(graph.contains_edge(&0, &1));
+        assert!(graph.contains_edge(&2, &0));
+        assert_eq!(vertices.originals(), [1, 2, 3, 4, 5]);
+        assert_eq!(vertices.internal(5), Some(4));
+        assert_eq!(vertices.internal(0), None);
+        assert_eq!(vertices.original(3), Some(4));
+    }
+
+    #[test]
+    fn dimacs_edge_count_matches_lines_or_distinct_edges() {
+        for (m, accepted) in [(2, true), (1, true), (3, false), (0, false)] {
+            let path = scratch("repeated.clq", &format!("p edge 3 {m}\ne 1 2\ne 2 1\n"));
+            let result = read_dimacs(&path);
+            assert_eq!(result.is_ok(), accepted, "M = {m}");
+        }
+
+        let path = scratch("truncated.clq", "p edge 4 3\ne 1 2\ne 2 3\n");
+        assert!(error(read_dimacs(&path)).ends_with(
+            "the 'p' line declares 3 edges, but the file has 2 'e' lines (2 distinct edges)"
+        ));
+    }
+
+    #[test]
+    fn malformed_dimacs_lines_are_errors() {
+        for (content, message) in [
+            ("e 1 2\np edge 2 1\n", "1: edge before the 'p' line"),
+            ("c only a comment\n", "0: missing 'p edge N M' line"),
+            ("p edge 2 1\np edge 2 1\n", "2: repeated 'p' line"),
+            ("p edge two 1\n", "1: invalid vertex count"),
+            ("p edge 2 one\n", "1: invalid edge count"),
+            ("p edge 2 1\ne 1\n", "2: unexpected line 'e 1'"),
+            ("p edge 2 1\ne 1 x\n", "2: vertex 'x' is not in 1..=2"),
+            ("p edge 2 1\ne 0 1\n", "2: vertex '0' is not in 1..=2"),
+            ("p edge 2 1\ne 1 3\n", "2: vertex '3' is not in 1..=2"),
+            ("p edge 2 1\ne 2 2\n", "2: loops are not supported"),
+            ("p edge 2 1\nx 1 2\n", "2: unexpected line 'x 1 2'"),
+        ] {
+            let path = scratch("malformed.clq", content);
+            let message = format!("{path}:{message}");
+            assert_eq!(error(read_dimacs(&path)), message);
+        }
+    }
+
+    #[test]
+    fn edge_lists_keep_declared_vertices_and_original_ids() {
+        let path = scratch("edges.txt", "# comment\n10 30\n\n30 20\n20 10\n10 30\n7\n");
+        let (graph, vertices) = read_edge_list(&path).unwrap();
+
+        assert_eq!(graph.order(), 4);
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(vertices.originals(), [10, 30, 20, 7]);
+        assert_eq!(vertices.internal(7), Some(3));
+        assert_eq!(graph.neighbors(&3).into_iter().flatten().count(), 0);
+    }
+
+    #[test]
+    fn malformed_edge_list_lines_are_errors() {
+        for (content, message) in [
+            ("1 2 3\n", "1: expected 'u v' or 'v', found '1 2 3'"),
+            ("1 2\na b\n", "2: invalid vertex 'a'"),
+            ("-1 2\n", "1: invalid vertex '-1'"),
+            ("4 4\n", "1: loop on vertex 4"),
+        ] {
+            let path = scratch("malformed.txt", content);
+            let message = format!("{path}:{message}");
+            assert_eq!(error(read_edge_list(&path)), message);
+        }
+    }
+
+    #[test]
+    fn dimacs_files_are_recognized() {
+        assert!(is_dimacs(&scratch("any.col", "1 2\n")).unwrap());
+        assert!(is_dimacs(&scratch("graph.txt", "\nc comment\np edge 1 0\n")).unwrap());
+        assert!(is_dimacs(&scratch("graph.txt", "p edge 1 0\n")).unwrap());
+        assert!(!is_dimacs(&scratch("graph.txt", "1 2\n")).unwrap());
+        assert!(!is_dimacs(&scratch("graph.txt", "")).unwrap());
+    }
+
+    #[test]
+    fn labelings_are_written_with_the_original_ids() {
+        let mut vertices = VertexMap::new();
+        for original in [10, 30, 20] {
+            vertices.insert(original);
+        }
+        let mut out = Vec::new();
+        write_labeling(&mut out, &Chromosome::new(vec![2, 0, 1]), &vertices).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "# weight: 3\n# |V0|: 1, |V1|: 1, |V2|: 1\n10 2\n30 0\n20 1\n"
+        );
+
+        let mut out = Vec::new();
+        write_labeling(
+            &mut out,
+            &Chromosome::new(vec![0, 2]),
+            &VertexMap::shifted(2, 1),
+        )
+        .unwrap();
+        assert!(String::from_utf8(out).unwrap().ends_with("1 0\n2 2\n"));
+    }
+
+    #[test]
+    fn labelings_are_read_back_with_the_original_ids() {
+        let mut vertices = VertexMap::new();
+        for original in [10, 30, 20, 7] {
+            vertices.insert(original);
+        }
+        let mut out = Vec::new();
+        write_labeling(&mut out, &Chromosome::new(vec![2, 0, 1, 1]), &vertices).unwrap();
+        let path = scratch("written.sol", &String::from_utf8(out).unwrap());
+
+        let (labeling, unlabeled) = read_labeling(&path, &vertices).unwrap();
+        assert_eq!(labeling.genes(), [2, 0, 1, 1]);
+        assert_eq!(labeling.fitness(), 4);
+        assert_eq!(unlabeled, []);
+
+        let path = scratch(
+            "partial.sol",
+            "# claimed
+
+20 2
+10 1
+",
+        );
+        let (labeling, unlabeled) = read_labeling(&path, &vertices).unwrap();
+        assert_eq!(labeling.genes(), [1, 0, 2, 0]);
+        assert_eq!(unlabeled, [1, 3]);
+    }
+
+    #[test]
+    fn malformed_labeling_lines_are_errors() {
+        let vertices = VertexMap::shifted(3, 1);
+        for (content, message) in [
+            (
+                "1 2
+2
+",
+                "2: expected 'vertex label', found '2'",
+            ),
+            (
+                "1 2 0
+",
+                "1: expected 'vertex label', found '1 2 0'",
+            ),
+            (
+                "1 two
+",
+                "1: expected 'vertex label', found '1 two'",
+            ),
+            (
+                "0 1
+",
+                "1: vertex 0 is not in the graph",
+            ),
+            (
+                "1 1
+# again
+1 2
+",
+                "3: vertex 1 is labeled more than once",
+            ),
+        ] {
+            let path = scratch("malformed.sol", content);
+            let Err(err) = read_labeling(&path, &vertices) else {
+                panic!("the labeling {content:?} was accepted");
+            };
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            assert_eq!(err.to_string(), format!("{path}:{message}"));
+        }
+    }
 }